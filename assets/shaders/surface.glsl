@@ -50,6 +50,7 @@ attribute vec2 a_normal;
 attribute float a_flow;
 uniform mat3 u_projection_matrix;
 uniform mat3 u_view_matrix;
+uniform mat3 u_model_matrix;
 uniform float u_simulation_time;
 uniform float u_height;
 uniform float u_texture_shift;
@@ -57,7 +58,8 @@ void main() {
     v_vt = a_vt + vec2(u_texture_shift - a_flow * u_simulation_time, 0.0);
     vec2 tangent = vec2(-a_normal.y, a_normal.x);
     // vec2 wind_shift = tangent * sin(u_simulation_time * 3.0) * a_vt.y * 0.02;
-    v_camera_pos = (u_view_matrix * vec3(a_pos, 1.0)).xy;
+    vec2 pos = (u_model_matrix * vec3(a_pos, 1.0)).xy;
+    v_camera_pos = (u_view_matrix * vec3(pos, 1.0)).xy;
     v_world_pos = (u_projection_matrix * vec3(v_camera_pos, 1.0)).xy;
     gl_Position = vec4(v_world_pos, 0.0, 1.0);
 }
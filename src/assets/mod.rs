@@ -14,10 +14,7 @@ pub struct Assets {
     pub guy: GuyAssets,
     pub surfaces: Listed<SurfaceAssets>,
     pub tiles: Listed<TileAssets>,
-    #[load(
-        load_with = "Listed::load_with_ext(&manager, &base_path.join(\"objects\"), Some(\"svg\"))"
-    )]
-    pub objects: Listed<Texture>,
+    pub objects: Listed<ObjectAssets>,
     #[load(load_with = "load_font(&manager, &base_path.join(\"Ludum-Dairy-0.2.0.ttf\"))")]
     pub font: geng::Font,
     #[load(ext = "svg")]
@@ -47,6 +44,35 @@ pub struct CannonAssets {
     pub shot: geng::Sound,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct TriggerConfig {
+    pub size: f32,
+}
+
+/// Burst shown when a hazard sends a guy back
+#[derive(Deserialize, Clone, Debug)]
+pub struct HazardConfig {
+    pub particle_count: usize,
+    pub particle_colors: Rc<Vec<Rgba<f32>>>,
+    pub particle_size: f32,
+    pub particle_speed: f32,
+}
+
+/// How wind zones push things and how they are shown
+#[derive(Deserialize, Clone, Debug)]
+pub struct WindConfig {
+    /// How quickly guys are pushed up to the wind speed
+    pub guy_drag: f32,
+    pub farticle_drag: f32,
+    /// Streaks per unit of area
+    pub streak_density: f32,
+    pub streak_lifetime: f32,
+    /// Streak length in seconds of wind movement
+    pub streak_length: f32,
+    pub streak_width: f32,
+    pub streak_color: Rgba<f32>,
+}
+
 #[derive(Deserialize, Clone, Debug)]
 pub struct CannonConfig {
     pub strength: f32,
@@ -61,6 +87,23 @@ pub struct CannonConfig {
 #[derive(Deserialize, Clone, Debug)]
 pub struct PortalConfig {
     pub size: f32,
+    /// Time after teleporting before any portal can be entered again
+    pub cooldown: f32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct CameraConfig {
+    /// How far ahead to look, in seconds of movement
+    pub look_ahead: f32,
+    pub max_look_ahead: f32,
+    /// Zoom out per unit of speed
+    pub speed_zoom: f32,
+    pub cannon_zoom: f32,
+    pub max_zoom: f32,
+    /// Space kept around the framed guys
+    pub framing_margin: f32,
+    /// Distance inside a camera zone over which it is blended in
+    pub zone_blend_distance: f32,
 }
 
 #[derive(geng::asset::Load, Deserialize, Clone, Debug)]
@@ -90,6 +133,9 @@ pub struct Config {
     pub snow_particle_colors: Rc<Vec<Rgba<f32>>>,
     pub cannon: CannonConfig,
     pub portal: PortalConfig,
+    pub trigger: TriggerConfig,
+    pub hazard: HazardConfig,
+    pub wind: WindConfig,
     pub stick_force_fadeout_speed: f32,
     pub max_penetration: f32,
     pub bubble_time: f32,
@@ -97,12 +143,14 @@ pub struct Config {
     pub bubble_acceleration: f32,
     pub bubble_target_speed: f32,
     pub camera_fov: f32,
+    pub camera: CameraConfig,
 }
 
 #[derive(geng::asset::Load)]
 pub struct SfxAssets {
     pub fart_recharge: geng::Sound,
     pub water_splash: geng::Sound,
+    pub hazard: geng::Sound,
     #[load(path = "music.mp3", postprocess = "make_looped")]
     pub old_music: geng::Sound,
     #[load(path = "KuviFart.wav", postprocess = "make_looped")]
@@ -169,6 +217,39 @@ pub struct FartConfig {
     pub farticle_lifetime: f32,
     #[serde(default = "create_true")]
     pub farticle_random_rotation: bool,
+
+    /// Physics overrides, the global config is used for missing values
+    pub fart_strength: Option<f32>,
+    pub fart_continued_force: Option<f32>,
+    pub force_fart_pressure_multiplier: Option<f32>,
+    pub fart_continuation_pressure_speed: Option<f32>,
+    #[serde(default = "one_f32")]
+    pub gravity_multiplier: f32,
+}
+
+/// Fart physics with the per fart type overrides applied
+pub struct FartPhysics {
+    pub strength: f32,
+    pub continued_force: f32,
+    pub force_pressure_multiplier: f32,
+    pub continuation_pressure_speed: f32,
+}
+
+impl FartConfig {
+    pub fn physics(&self, config: &Config) -> FartPhysics {
+        FartPhysics {
+            strength: self.fart_strength.unwrap_or(config.fart_strength),
+            continued_force: self
+                .fart_continued_force
+                .unwrap_or(config.fart_continued_force),
+            force_pressure_multiplier: self
+                .force_fart_pressure_multiplier
+                .unwrap_or(config.force_fart_pressure_multiplier),
+            continuation_pressure_speed: self
+                .fart_continuation_pressure_speed
+                .unwrap_or(config.fart_continuation_pressure_speed),
+        }
+    }
 }
 
 fn one_f32() -> f32 {
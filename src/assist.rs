@@ -0,0 +1,125 @@
+use super::*;
+
+/// Options cycled through by one-switch scanning
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScanOption {
+    RollLeft,
+    RollRight,
+    Fart,
+}
+
+impl ScanOption {
+    const ALL: [Self; 3] = [Self::RollLeft, Self::RollRight, Self::Fart];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::RollLeft => "<",
+            Self::RollRight => ">",
+            Self::Fart => "fart",
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Assists {
+    /// Roll direction that is toggled on, used instead of holding the roll buttons
+    roll_toggle: Option<Action>,
+    scan_index: usize,
+    scan_timer: f32,
+    /// Input from all sources before assists, to notice presses
+    raw_input: Input,
+}
+
+impl Assists {
+    fn toggle_roll(&mut self, action: Action) {
+        self.roll_toggle = if self.roll_toggle == Some(action) {
+            None
+        } else {
+            Some(action)
+        };
+    }
+
+    fn scan_option(&self) -> ScanOption {
+        ScanOption::ALL[self.scan_index]
+    }
+
+    /// Advances scanning while the switch is released
+    pub fn update(&mut self, settings: &Settings, delta_time: f32) {
+        // The fart input acts as the switch
+        if !settings.one_switch || self.raw_input.force_fart {
+            self.scan_timer = 0.0;
+            return;
+        }
+        self.scan_timer += delta_time;
+        if self.scan_timer >= settings.scan_interval {
+            self.scan_timer = 0.0;
+            self.scan_index = (self.scan_index + 1) % ScanOption::ALL.len();
+        }
+    }
+
+    /// Replaces input merged from all sources according to the enabled assists
+    pub fn apply_input(&mut self, settings: &Settings, input: &mut Input) {
+        let raw_input = mem::replace(&mut self.raw_input, input.clone());
+        if settings.toggle_roll {
+            if input.roll_left > 0.5 && raw_input.roll_left <= 0.5 {
+                self.toggle_roll(Action::RollLeft);
+            }
+            if input.roll_right > 0.5 && raw_input.roll_right <= 0.5 {
+                self.toggle_roll(Action::RollRight);
+            }
+            input.roll_left = if self.roll_toggle == Some(Action::RollLeft) {
+                1.0
+            } else {
+                0.0
+            };
+            input.roll_right = if self.roll_toggle == Some(Action::RollRight) {
+                1.0
+            } else {
+                0.0
+            };
+        }
+        if settings.one_switch {
+            let switch_held = input.force_fart;
+            *input = Input::default();
+            if switch_held {
+                match self.scan_option() {
+                    ScanOption::RollLeft => input.roll_left = 1.0,
+                    ScanOption::RollRight => input.roll_right = 1.0,
+                    ScanOption::Fart => input.force_fart = true,
+                }
+            }
+        }
+    }
+}
+
+impl Game {
+    pub fn update_assists(&mut self, delta_time: f32) {
+        self.assists.update(&self.settings, delta_time);
+    }
+
+    pub fn draw_assists(&self, framebuffer: &mut ugli::Framebuffer) {
+        if !self.settings.one_switch || self.show_customizer || self.pause_menu.is_some() {
+            return;
+        }
+        let camera = geng::Camera2d {
+            center: vec2::ZERO,
+            rotation: 0.0,
+            fov: 10.0,
+        };
+        for (index, option) in ScanOption::ALL.into_iter().enumerate() {
+            let color = if option == self.assists.scan_option() {
+                Rgba::new(0.5, 0.5, 1.0, 1.0)
+            } else {
+                Rgba::new(0.5, 0.5, 1.0, 0.3)
+            };
+            self.assets.get().font.draw(
+                framebuffer,
+                &camera,
+                option.label(),
+                vec2::splat(geng::TextAlign::CENTER),
+                mat3::translate(vec2((index as f32 - 1.0) * 2.0, -4.0)) * mat3::scale_uniform(0.7),
+                color,
+            );
+        }
+    }
+}
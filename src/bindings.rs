@@ -0,0 +1,294 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    RollLeft,
+    RollRight,
+    Fart,
+    Restart,
+    Checkpoint,
+    Emote(usize),
+    Pause,
+}
+
+impl Action {
+    pub fn name(&self) -> String {
+        match self {
+            Self::RollLeft => "roll left".to_owned(),
+            Self::RollRight => "roll right".to_owned(),
+            Self::Fart => "fart".to_owned(),
+            Self::Restart => "restart".to_owned(),
+            Self::Checkpoint => "checkpoint".to_owned(),
+            Self::Emote(index) => format!("emote {}", index + 1),
+            Self::Pause => "pause".to_owned(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Binding {
+    Key {
+        key: geng::Key,
+        /// Only triggers while Ctrl is held
+        #[serde(default)]
+        ctrl: bool,
+    },
+    Mouse(geng::MouseButton),
+    GamepadButton(gilrs::Button),
+    GamepadAxis {
+        axis: gilrs::Axis,
+        positive: bool,
+    },
+}
+
+impl Binding {
+    fn key(key: geng::Key) -> Self {
+        Self::Key { key, ctrl: false }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Key { key, ctrl: false } => format!("{key:?}"),
+            Self::Key { key, ctrl: true } => format!("Ctrl-{key:?}"),
+            Self::Mouse(button) => format!("Mouse{button:?}"),
+            Self::GamepadButton(button) => format!("{button:?}"),
+            Self::GamepadAxis { axis, positive } => {
+                format!("{axis:?}{}", if *positive { "+" } else { "-" })
+            }
+        }
+    }
+
+    /// Binding for an input event, used when rebinding an action
+    pub fn from_event(geng: &Geng, event: &geng::Event) -> Option<Self> {
+        match *event {
+            geng::Event::KeyDown { key } => Some(Self::Key {
+                key,
+                ctrl: geng.window().is_key_pressed(geng::Key::LCtrl),
+            }),
+            geng::Event::MouseDown { button, .. } => Some(Self::Mouse(button)),
+            geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::ButtonPressed(button, ..),
+                ..
+            }) => Some(Self::GamepadButton(button)),
+            geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::AxisChanged(axis, value, ..),
+                ..
+            }) if value.abs() > 0.5 => Some(Self::GamepadAxis {
+                axis,
+                positive: value > 0.0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Bindings {
+    actions: Vec<(Action, Vec<Binding>)>,
+}
+
+impl Bindings {
+    pub fn new(emote_count: usize) -> Self {
+        let mut actions = vec![
+            (
+                Action::RollLeft,
+                vec![
+                    Binding::key(geng::Key::A),
+                    Binding::key(geng::Key::Left),
+                    Binding::GamepadAxis {
+                        axis: gilrs::Axis::LeftStickX,
+                        positive: false,
+                    },
+                    Binding::GamepadAxis {
+                        axis: gilrs::Axis::RightStickX,
+                        positive: false,
+                    },
+                ],
+            ),
+            (
+                Action::RollRight,
+                vec![
+                    Binding::key(geng::Key::D),
+                    Binding::key(geng::Key::Right),
+                    Binding::GamepadAxis {
+                        axis: gilrs::Axis::LeftStickX,
+                        positive: true,
+                    },
+                    Binding::GamepadAxis {
+                        axis: gilrs::Axis::RightStickX,
+                        positive: true,
+                    },
+                ],
+            ),
+            (
+                Action::Fart,
+                vec![
+                    Binding::key(geng::Key::W),
+                    Binding::key(geng::Key::Up),
+                    Binding::key(geng::Key::Space),
+                    Binding::Mouse(geng::MouseButton::Left),
+                    Binding::GamepadButton(gilrs::Button::South),
+                ],
+            ),
+            (
+                Action::Restart,
+                vec![Binding::Key {
+                    key: geng::Key::R,
+                    ctrl: true,
+                }],
+            ),
+            (
+                Action::Checkpoint,
+                vec![
+                    Binding::key(geng::Key::R),
+                    Binding::GamepadButton(gilrs::Button::Select),
+                ],
+            ),
+        ];
+        let emote_keys = [
+            geng::Key::Num1,
+            geng::Key::Num2,
+            geng::Key::Num3,
+            geng::Key::Num4,
+            geng::Key::Num5,
+            geng::Key::Num6,
+            geng::Key::Num7,
+            geng::Key::Num8,
+            geng::Key::Num9,
+        ];
+        for index in 0..emote_count {
+            let bindings = match emote_keys.get(index) {
+                Some(&key) => vec![Binding::key(key)],
+                None => vec![],
+            };
+            actions.push((Action::Emote(index), bindings));
+        }
+        actions.push((
+            Action::Pause,
+            vec![
+                Binding::key(geng::Key::Escape),
+                Binding::GamepadButton(gilrs::Button::Start),
+            ],
+        ));
+        Self { actions }
+    }
+
+    pub fn load(emote_count: usize) -> Self {
+        preferences::load("bindings").unwrap_or_else(|| Self::new(emote_count))
+    }
+
+    pub fn save(&self) {
+        preferences::save("bindings", self);
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = Action> + '_ {
+        self.actions.iter().map(|&(action, _)| action)
+    }
+
+    pub fn get(&self, action: Action) -> &[Binding] {
+        self.actions
+            .iter()
+            .find(|(other, _)| *other == action)
+            .map_or(&[], |(_, bindings)| bindings)
+    }
+
+    pub fn label(&self, action: Action) -> String {
+        let bindings = self.get(action);
+        if bindings.is_empty() {
+            return format!("{}: none", action.name());
+        }
+        let names: Vec<String> = bindings.iter().map(Binding::name).collect();
+        format!("{}: {}", action.name(), names.join(", "))
+    }
+
+    /// Adds a binding to the action, removing it from any other action
+    pub fn bind(&mut self, action: Action, binding: Binding) {
+        for (_, bindings) in &mut self.actions {
+            bindings.retain(|&other| other != binding);
+        }
+        match self.actions.iter_mut().find(|(other, _)| *other == action) {
+            Some((_, bindings)) => bindings.push(binding),
+            None => self.actions.push((action, vec![binding])),
+        }
+        self.save();
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        if let Some((_, bindings)) = self.actions.iter_mut().find(|(other, _)| *other == action) {
+            bindings.clear();
+        }
+        self.save();
+    }
+
+    /// How much the action is currently held, from 0 to 1
+    pub fn value(
+        &self,
+        geng: &Geng,
+        gamepad: Option<gilrs::GamepadId>,
+        deadzone: f32,
+        action: Action,
+    ) -> f32 {
+        let window = geng.window();
+        let gilrs = geng.gilrs();
+        let gamepad = gamepad.map(|id| gilrs.gamepad(id));
+        let mut result = 0.0;
+        for binding in self.get(action) {
+            result += match *binding {
+                Binding::Key { key, ctrl } => {
+                    let pressed = window.is_key_pressed(key)
+                        && (!ctrl || window.is_key_pressed(geng::Key::LCtrl));
+                    if pressed {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                Binding::Mouse(button) => {
+                    if window.is_button_pressed(button) {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+                Binding::GamepadButton(button) => {
+                    match gamepad.as_ref().and_then(|g| g.button_data(button)) {
+                        Some(button) if button.is_pressed() => 1.0,
+                        _ => 0.0,
+                    }
+                }
+                Binding::GamepadAxis { axis, positive } => {
+                    match gamepad.as_ref().and_then(|g| g.axis_data(axis)) {
+                        Some(axis) => {
+                            let value = if positive {
+                                axis.value()
+                            } else {
+                                -axis.value()
+                            };
+                            if value < deadzone {
+                                0.0
+                            } else {
+                                value
+                            }
+                        }
+                        None => 0.0,
+                    }
+                }
+            };
+        }
+        result.clamp(0.0, 1.0)
+    }
+
+    /// Actions that were just pressed by this event
+    pub fn triggered(&self, geng: &Geng, event: &geng::Event) -> Vec<Action> {
+        let Some(pressed) = Binding::from_event(geng, event) else { return vec![] };
+        // Axes are only used for held actions
+        if let Binding::GamepadAxis { .. } = pressed {
+            return vec![];
+        }
+        self.actions
+            .iter()
+            .filter(|(_, bindings)| bindings.contains(&pressed))
+            .map(|&(action, _)| action)
+            .collect()
+    }
+}
@@ -0,0 +1,113 @@
+use super::*;
+
+/// Smoothed camera state for following one or several guys
+pub struct CameraDirector {
+    look_ahead: vec2<f32>,
+    zoom: f32,
+    /// Parallax multiplier from the current camera zone
+    pub parallax: f32,
+}
+
+impl Default for CameraDirector {
+    fn default() -> Self {
+        Self {
+            look_ahead: vec2::ZERO,
+            zoom: 1.0,
+            parallax: 1.0,
+        }
+    }
+}
+
+impl Game {
+    /// Moves the camera to frame the target guys, looking ahead of their movement
+    /// and zooming out when they are fast or about to be shot from a cannon
+    pub fn direct_camera(
+        &self,
+        director: &mut CameraDirector,
+        camera: &mut geng::Camera2d,
+        targets: &[Id],
+        delta_time: f32,
+    ) {
+        let config = &self.config.camera;
+        let guys: Vec<&Guy> = targets.iter().filter_map(|id| self.guys.get(id)).collect();
+        if guys.is_empty() {
+            return;
+        }
+        let bounds = Aabb2::points_bounding_box(guys.iter().map(|guy| guy.state.pos));
+        let vel = guys
+            .iter()
+            .map(|guy| guy.state.vel)
+            .fold(vec2::ZERO, |a, b| a + b)
+            / guys.len() as f32;
+
+        let mut look_ahead = vel * config.look_ahead;
+        if look_ahead.len() > config.max_look_ahead {
+            look_ahead = look_ahead * (config.max_look_ahead / look_ahead.len());
+        }
+        director.look_ahead += (look_ahead - director.look_ahead) * (delta_time * 2.0).min(1.0);
+
+        let speed = guys
+            .iter()
+            .map(|guy| guy.state.vel.len())
+            .fold(0.0, f32::max);
+        let mut zoom = 1.0 + speed * config.speed_zoom;
+        if guys.iter().any(|guy| guy.state.cannon_timer.is_some()) {
+            zoom = zoom.max(config.cannon_zoom);
+        }
+        let zoom = zoom.min(config.max_zoom);
+        director.zoom += (zoom - director.zoom) * delta_time.min(1.0);
+
+        // Camera zones are blended in as the guys get deeper inside
+        let zone = self.level.camera_zone_at(bounds.center());
+        let weight = zone.map_or(0.0, |zone| {
+            zone.weight(bounds.center(), config.zone_blend_distance)
+        });
+
+        let mut target_center = bounds.center() + director.look_ahead;
+        if let Some(center) = zone.and_then(|zone| zone.center) {
+            target_center += (center - target_center) * weight;
+        }
+        if self.show_customizer && matches!(targets, [id] if Some(*id) == self.my_guy) {
+            target_center.x += 1.0;
+        }
+        camera.center += (target_center - camera.center) * (delta_time * 5.0).min(1.0);
+
+        let zone_parallax = zone.map_or(1.0, |zone| zone.parallax);
+        director.parallax = 1.0 + (zone_parallax - 1.0) * weight;
+
+        // Gravity zones may turn the camera so that their gravity points down
+        let gravity = self.level.gravity_at(bounds.center(), &self.assets.get());
+        let target_rotation = match gravity {
+            Some(local) if local.rotate_camera && self.editor.is_none() => {
+                if local.gravity == vec2::ZERO {
+                    camera.rotation
+                } else {
+                    local.gravity.arg() + f32::PI / 2.0
+                }
+            }
+            _ => 0.0,
+        };
+        let rotation_delta =
+            (target_rotation - camera.rotation + f32::PI).rem_euclid(2.0 * f32::PI) - f32::PI;
+        camera.rotation += rotation_delta * (delta_time * 3.0).min(1.0);
+
+        // The editor controls the zoom by itself
+        if self.editor.is_some() {
+            return;
+        }
+        let mut base_fov = self.settings.camera_fov;
+        if let Some(zone) = zone {
+            base_fov += (zone.fov - base_fov) * weight;
+        }
+        let aspect = self.framebuffer_size.x / self.framebuffer_size.y;
+        let target_fov = (base_fov * director.zoom)
+            .max(bounds.height() + config.framing_margin)
+            .max(bounds.width() / aspect + config.framing_margin);
+        camera.fov += (target_fov - camera.fov) * (delta_time * 2.0).min(1.0);
+    }
+
+    /// Parallax of a layer with the camera zone tweak applied
+    pub fn layer_parallax(&self, layer: &LevelLayer) -> vec2<f32> {
+        vec2(1.0, 1.0) + (layer.parallax - vec2(1.0, 1.0)) * self.camera_parallax
+    }
+}
@@ -4,6 +4,7 @@ use super::*;
 pub enum UiMessage {
     Play,
     RandomizeSkin,
+    OpenLoadMenu,
 }
 
 impl Game {
@@ -11,6 +12,10 @@ impl Game {
         if !self.show_customizer {
             return;
         }
+        if self.show_load_menu {
+            self.draw_load_menu(framebuffer);
+            return;
+        }
         let camera = geng::Camera2d {
             center: vec2::ZERO,
             rotation: 0.0,
@@ -39,7 +44,7 @@ impl Game {
             self.assets.get().font.draw(
                 framebuffer,
                 &camera,
-                &self.customization.name,
+                &self.name_input.display(&self.customization.name),
                 vec2::splat(geng::TextAlign::CENTER),
                 mat3::translate(vec2(0.0, 3.0)),
                 Rgba::new(0.5, 0.5, 1.0, 1.0),
@@ -53,12 +58,18 @@ impl Game {
                 event,
                 geng::Event::KeyDown {
                     key: geng::Key::Enter,
-                } | geng::Event::Gamepad(gilrs::Event {
-                    event: gilrs::EventType::ButtonPressed(gilrs::Button::Start, ..),
-                    ..
-                })
+                }
             ) {
                 self.show_customizer = true;
+                self.ui_controller.reset_selection();
+            }
+            if self.editor.is_none()
+                && self
+                    .bindings
+                    .triggered(&self.geng, event)
+                    .contains(&Action::Pause)
+            {
+                self.open_pause_menu();
             }
             return;
         }
@@ -66,44 +77,39 @@ impl Game {
             .ui_controller
             .handle_event(event, self.buttons.clone())
             .into_iter();
+        let has_selection = self.ui_controller.has_selection();
         let msgs = msgs.chain(
-            matches!(
-                event,
+            match event {
                 geng::Event::KeyDown {
                     key: geng::Key::Enter,
-                } | geng::Event::Gamepad(gilrs::Event {
+                } => !has_selection,
+                geng::Event::Gamepad(gilrs::Event {
                     event: gilrs::EventType::ButtonPressed(gilrs::Button::Start, ..),
                     ..
-                })
-            )
+                }) => true,
+                _ => false,
+            }
             .then_some(UiMessage::Play),
         );
         for msg in msgs {
             match msg {
                 UiMessage::Play => {
                     self.show_customizer = false;
+                    self.customization.name =
+                        CustomizationOptions::sanitize_name(&self.customization.name);
                     preferences::save("customization", &self.customization);
                 }
                 UiMessage::RandomizeSkin => {
                     self.customization.colors = GuyColors::random();
                 }
-            }
-        }
-        if let geng::Event::KeyDown { key } = event {
-            let s = format!("{key:?}");
-            let c = if s.len() == 1 {
-                Some(s.as_str())
-            } else {
-                s.strip_prefix("Num")
-            };
-            if let Some(c) = c {
-                if self.customization.name.len() < 15 {
-                    self.customization.name.push_str(c);
+                UiMessage::OpenLoadMenu => {
+                    self.show_load_menu = true;
+                    self.ui_controller.reset_selection();
+                    return;
                 }
             }
-            if *key == geng::Key::Backspace {
-                self.customization.name.pop();
-            }
         }
+        self.name_input
+            .handle_event(&mut self.customization.name, event);
     }
 }
@@ -31,6 +31,9 @@ impl EditorState {
             tool_constructor::<ProgressTool>(geng, assets),
             tool_constructor::<CannonTool>(geng, assets),
             tool_constructor::<PortalTool>(geng, assets),
+            tool_constructor::<CameraZoneTool>(geng, assets),
+            tool_constructor::<TriggerTool>(geng, assets),
+            tool_constructor::<WindZoneTool>(geng, assets),
         ];
         let selected_tool_index = 0;
         Self {
@@ -77,14 +80,10 @@ impl Game {
     }
 
     pub fn snap_position(&self, level: &Level, pos: vec2<f32>) -> vec2<f32> {
-        let closest_point = itertools::chain![
-            level
-                .all_surfaces()
-                .flat_map(|surface| [surface.p1, surface.p2]),
-            level.all_tiles().flat_map(|tile| tile.vertices)
-        ]
-        .filter(|&p| (pos - p).len() < self.config.snap_distance)
-        .min_by_key(|&p| r32((pos - p).len()));
+        let closest_point = level
+            .points_near(Aabb2::point(pos).extend_uniform(self.config.snap_distance))
+            .filter(|&p| (pos - p).len() < self.config.snap_distance)
+            .min_by_key(|&p| r32((pos - p).len()));
         closest_point.unwrap_or(pos)
     }
 
@@ -141,6 +140,13 @@ impl Game {
                         (editor.selected_tool_index + 1) % editor.available_tools.len();
                     editor.tool = editor.available_tools[editor.selected_tool_index].create();
                 }
+                geng::Key::P => {
+                    editor.tool.apply_config(
+                        &editor.cursor,
+                        &mut self.level,
+                        editor.selected_layer,
+                    );
+                }
                 geng::Key::T => {
                     if let Some(guy) = self.my_guy.and_then(|id| self.guys.get_mut(&id)) {
                         guy.state.pos = editor.cursor.world_pos;
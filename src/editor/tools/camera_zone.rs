@@ -0,0 +1,256 @@
+use super::*;
+
+pub struct CameraZoneToolConfig {
+    snap_distance: f32,
+    fov: f32,
+    parallax: f32,
+}
+
+impl EditorToolConfig for CameraZoneToolConfig {
+    fn default(assets: &AssetsHandle) -> Self {
+        Self {
+            snap_distance: assets.get().config.snap_distance,
+            fov: assets.get().config.camera_fov * 2.0,
+            parallax: 1.0,
+        }
+    }
+}
+
+enum Drag {
+    Create { start: vec2<f32> },
+    Resize { index: usize, anchor: vec2<f32> },
+}
+
+pub struct CameraZoneTool {
+    geng: Geng,
+    assets: AssetsHandle,
+    drag: Option<Drag>,
+    config: CameraZoneToolConfig,
+}
+
+fn corners(rect: Aabb2<f32>) -> [vec2<f32>; 4] {
+    [
+        rect.bottom_left(),
+        rect.bottom_right(),
+        rect.top_right(),
+        rect.top_left(),
+    ]
+}
+
+impl CameraZoneTool {
+    fn find_hovered_zone(&self, cursor: &Cursor, level: &Level) -> Option<usize> {
+        level
+            .camera_zones
+            .iter()
+            .enumerate()
+            .filter(|(_index, zone)| zone.rect.contains(cursor.world_pos))
+            .min_by_key(|(_index, zone)| r32(zone.rect.width() * zone.rect.height()))
+            .map(|(index, _zone)| index)
+    }
+
+    /// Zone index, hovered corner and the opposite corner
+    fn find_hovered_corner(
+        &self,
+        cursor: &Cursor,
+        level: &Level,
+    ) -> Option<(usize, vec2<f32>, vec2<f32>)> {
+        level
+            .camera_zones
+            .iter()
+            .enumerate()
+            .flat_map(|(index, zone)| {
+                let corners = corners(zone.rect);
+                (0..4).map(move |i| (index, corners[i], corners[(i + 2) % 4]))
+            })
+            .filter(|(_index, corner, _anchor)| {
+                (*corner - cursor.world_pos).len() < self.config.snap_distance * 2.0
+            })
+            .min_by_key(|(_index, corner, _anchor)| r32((*corner - cursor.world_pos).len()))
+    }
+}
+
+impl EditorTool for CameraZoneTool {
+    type Config = CameraZoneToolConfig;
+    fn new(geng: &Geng, assets: &AssetsHandle, config: CameraZoneToolConfig) -> Self {
+        Self {
+            geng: geng.clone(),
+            assets: assets.clone(),
+            config,
+            drag: None,
+        }
+    }
+    fn draw(
+        &self,
+        cursor: &Cursor,
+        level: &Level,
+        selected_layer: usize,
+        camera: &geng::Camera2d,
+        framebuffer: &mut ugli::Framebuffer,
+    ) {
+        let hovered = self.find_hovered_zone(cursor, level);
+        for (index, zone) in level.camera_zones.iter().enumerate() {
+            let corners = corners(zone.rect);
+            for i in 0..4 {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(
+                        Segment(corners[i], corners[(i + 1) % 4]),
+                        0.05,
+                        Rgba::new(0.0, 0.0, 1.0, 0.5),
+                    ),
+                );
+            }
+            if hovered == Some(index) {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Quad::new(zone.rect, Rgba::new(0.0, 0.0, 1.0, 0.1)),
+                );
+            }
+            if let Some(center) = zone.center {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Quad::new(
+                        Aabb2::point(center).extend_uniform(0.2),
+                        Rgba::new(0.0, 0.0, 1.0, 0.5),
+                    ),
+                );
+            }
+            self.assets.get().font.draw(
+                framebuffer,
+                camera,
+                &format!("fov {:.1} parallax {:.1}", zone.fov, zone.parallax),
+                vec2::splat(geng::TextAlign::LEFT),
+                mat3::translate(zone.rect.top_left() + vec2(0.1, -0.5)) * mat3::scale_uniform(0.4),
+                Rgba::new(0.0, 0.0, 1.0, 0.5),
+            );
+        }
+        let preview = match self.drag {
+            Some(Drag::Create { start }) => Some(Aabb2::from_corners(start, cursor.world_pos)),
+            Some(Drag::Resize { anchor, .. }) => {
+                Some(Aabb2::from_corners(anchor, cursor.world_pos))
+            }
+            None => None,
+        };
+        if let Some(rect) = preview {
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                camera,
+                &draw2d::Quad::new(rect, Rgba::new(1.0, 0.0, 0.0, 0.2)),
+            );
+        } else if let Some((_index, corner, _anchor)) = self.find_hovered_corner(cursor, level) {
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                camera,
+                &draw2d::Quad::new(
+                    Aabb2::point(corner).extend_uniform(0.2),
+                    Rgba::new(1.0, 0.0, 0.0, 0.5),
+                ),
+            );
+        }
+    }
+    fn handle_event(
+        &mut self,
+        cursor: &Cursor,
+        event: &geng::Event,
+        level: &mut Level,
+        selected_layer: usize,
+    ) {
+        match event {
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Left,
+                ..
+            } => {
+                self.drag = Some(match self.find_hovered_corner(cursor, level) {
+                    Some((index, _corner, anchor)) => Drag::Resize { index, anchor },
+                    None => Drag::Create {
+                        start: cursor.world_pos,
+                    },
+                });
+            }
+            geng::Event::MouseUp {
+                button: geng::MouseButton::Left,
+                ..
+            } => match self.drag.take() {
+                Some(Drag::Create { start }) => {
+                    let rect = Aabb2::from_corners(start, cursor.world_pos);
+                    if rect.width() > self.config.snap_distance
+                        && rect.height() > self.config.snap_distance
+                    {
+                        level.modify().camera_zones.push(CameraZone {
+                            rect,
+                            fov: self.config.fov,
+                            center: None,
+                            parallax: self.config.parallax,
+                        });
+                    }
+                }
+                Some(Drag::Resize { index, anchor }) => {
+                    level.modify().camera_zones[index].rect =
+                        Aabb2::from_corners(anchor, cursor.world_pos);
+                }
+                None => {}
+            },
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Right,
+                ..
+            } => {
+                if let Some(index) = self.find_hovered_zone(cursor, level) {
+                    level.modify().camera_zones.remove(index);
+                }
+            }
+            geng::Event::KeyDown { key: geng::Key::F } => {
+                if let Some(index) = self.find_hovered_zone(cursor, level) {
+                    let zone = &mut level.modify().camera_zones[index];
+                    zone.center = match zone.center {
+                        Some(_) => None,
+                        None => Some(cursor.world_pos),
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, _selected_layer: usize) {
+        if let Some(index) = self.find_hovered_zone(cursor, level) {
+            let zone = &mut level.modify().camera_zones[index];
+            zone.fov = self.config.fov;
+            zone.parallax = self.config.parallax;
+        }
+    }
+
+    const NAME: &'static str = "Camera";
+
+    fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
+        use geng::ui::*;
+
+        let fov_down = Button::new(cx, "fov -");
+        if fov_down.was_clicked() {
+            self.config.fov = (self.config.fov - 0.5).max(1.0);
+        }
+        let fov_up = Button::new(cx, "fov +");
+        if fov_up.was_clicked() {
+            self.config.fov += 0.5;
+        }
+        let parallax_down = Button::new(cx, "parallax -");
+        if parallax_down.was_clicked() {
+            self.config.parallax = (self.config.parallax - 0.1).max(0.0);
+        }
+        let parallax_up = Button::new(cx, "parallax +");
+        if parallax_up.was_clicked() {
+            self.config.parallax += 0.1;
+        }
+        column![
+            row![fov_down.uniform_padding(8.0), fov_up.uniform_padding(8.0)],
+            row![
+                parallax_down.uniform_padding(8.0),
+                parallax_up.uniform_padding(8.0),
+            ],
+        ]
+        .align(vec2(0.0, 0.0))
+        .boxed()
+    }
+}
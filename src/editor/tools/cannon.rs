@@ -2,12 +2,23 @@ use super::*;
 
 pub struct CannonToolConfig {
     snap_distance: f32,
+    default_strength: f32,
+    default_shoot_time: f32,
+    strength: Option<f32>,
+    shoot_time: Option<f32>,
+    aim: CannonAim,
 }
 
 impl EditorToolConfig for CannonToolConfig {
     fn default(assets: &AssetsHandle) -> Self {
+        let assets = assets.get();
         Self {
-            snap_distance: assets.get().config.snap_distance,
+            snap_distance: assets.config.snap_distance,
+            default_strength: assets.config.cannon.strength,
+            default_shoot_time: assets.config.cannon.shoot_time,
+            strength: None,
+            shoot_time: None,
+            aim: CannonAim::Fixed,
         }
     }
 }
@@ -30,6 +41,12 @@ impl CannonTool {
             .min_by_key(|(_index, cannon)| r32((cannon.pos - cursor.world_pos).len()))
             .map(|(index, _cannon)| index)
     }
+
+    fn configure(&self, cannon: &mut Cannon) {
+        cannon.strength = self.config.strength;
+        cannon.shoot_time = self.config.shoot_time;
+        cannon.aim = self.config.aim;
+    }
 }
 
 impl EditorTool for CannonTool {
@@ -50,6 +67,24 @@ impl EditorTool for CannonTool {
         camera: &geng::Camera2d,
         framebuffer: &mut ugli::Framebuffer,
     ) {
+        for cannon in &level.cannons {
+            let range = match cannon.aim {
+                CannonAim::Fixed => continue,
+                CannonAim::Oscillate { amplitude, .. } => amplitude,
+                CannonAim::Player { range, .. } => range,
+            };
+            for rot in [cannon.rot - range, cannon.rot + range] {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(
+                        Segment(cannon.pos, cannon.pos + vec2(1.5, 0.0).rotate(rot)),
+                        0.05,
+                        Rgba::new(1.0, 0.5, 0.0, 0.5),
+                    ),
+                );
+            }
+        }
         if let Some(start) = self.start_drag {
             self.geng.draw2d().draw2d(
                 framebuffer,
@@ -70,6 +105,19 @@ impl EditorTool for CannonTool {
                     Rgba::new(1.0, 0.0, 0.0, 0.5),
                 ),
             );
+            self.assets.get().font.draw(
+                framebuffer,
+                camera,
+                &format!(
+                    "strength {:.1} delay {:.1} {}",
+                    cannon.strength.unwrap_or(self.config.default_strength),
+                    cannon.shoot_time.unwrap_or(self.config.default_shoot_time),
+                    aim_name(&cannon.aim),
+                ),
+                vec2::splat(geng::TextAlign::CENTER),
+                mat3::translate(cannon.pos + vec2(0.0, 1.0)) * mat3::scale_uniform(0.4),
+                Rgba::new(0.0, 0.0, 1.0, 0.5),
+            );
         }
     }
     fn handle_event(
@@ -89,10 +137,15 @@ impl EditorTool for CannonTool {
                 ..
             } => {
                 if let Some(start) = self.start_drag.take() {
-                    level.modify().cannons.push(Cannon {
+                    let mut cannon = Cannon {
                         pos: start,
                         rot: (cursor.world_pos - start).arg(),
-                    });
+                        strength: None,
+                        shoot_time: None,
+                        aim: CannonAim::Fixed,
+                    };
+                    self.configure(&mut cannon);
+                    level.modify().cannons.push(cannon);
                 }
             }
             geng::Event::MouseDown {
@@ -107,10 +160,107 @@ impl EditorTool for CannonTool {
         }
     }
 
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, _selected_layer: usize) {
+        if let Some(index) = self.find_hovered_cannon(cursor, level) {
+            self.configure(&mut level.modify().cannons[index]);
+        }
+    }
+
     const NAME: &'static str = "Cannon";
 
-    fn ui<'a>(&'a mut self, _cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
+    fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
         use geng::ui::*;
-        geng::ui::Void.fixed_size(vec2::ZERO).boxed()
+
+        let config = &mut self.config;
+        let strength_down = Button::new(cx, "strength -");
+        if strength_down.was_clicked() {
+            let strength = config.strength.unwrap_or(config.default_strength);
+            config.strength = Some((strength - 1.0).max(0.0));
+        }
+        let strength_up = Button::new(cx, "strength +");
+        if strength_up.was_clicked() {
+            config.strength = Some(config.strength.unwrap_or(config.default_strength) + 1.0);
+        }
+        let delay_down = Button::new(cx, "delay -");
+        if delay_down.was_clicked() {
+            let shoot_time = config.shoot_time.unwrap_or(config.default_shoot_time);
+            config.shoot_time = Some((shoot_time - 0.25).max(0.0));
+        }
+        let delay_up = Button::new(cx, "delay +");
+        if delay_up.was_clicked() {
+            config.shoot_time = Some(config.shoot_time.unwrap_or(config.default_shoot_time) + 0.25);
+        }
+        let aim = Button::new(cx, aim_name(&config.aim));
+        if aim.was_clicked() {
+            config.aim = match config.aim {
+                CannonAim::Fixed => CannonAim::Oscillate {
+                    amplitude: f32::PI / 4.0,
+                    period: 2.0,
+                },
+                CannonAim::Oscillate { amplitude, .. } => CannonAim::Player {
+                    range: amplitude,
+                    speed: 2.0,
+                },
+                CannonAim::Player { .. } => CannonAim::Fixed,
+            };
+        }
+        let sweep_down = Button::new(cx, "sweep -");
+        let sweep_up = Button::new(cx, "sweep +");
+        let sweep_delta = match (sweep_down.was_clicked(), sweep_up.was_clicked()) {
+            (true, false) => -f32::PI / 12.0,
+            (false, true) => f32::PI / 12.0,
+            _ => 0.0,
+        };
+        let rate_down = Button::new(cx, "rate -");
+        let rate_up = Button::new(cx, "rate +");
+        let rate_delta = match (rate_down.was_clicked(), rate_up.was_clicked()) {
+            (true, false) => -0.5,
+            (false, true) => 0.5,
+            _ => 0.0,
+        };
+        match &mut config.aim {
+            CannonAim::Fixed => {}
+            CannonAim::Oscillate { amplitude, period } => {
+                *amplitude = (*amplitude + sweep_delta).clamp(0.0, f32::PI);
+                // Faster rate means a shorter period
+                *period = (*period - rate_delta).max(0.5);
+            }
+            CannonAim::Player { range, speed } => {
+                *range = (*range + sweep_delta).clamp(0.0, f32::PI);
+                *speed = (*speed + rate_delta).max(0.5);
+            }
+        }
+        let reset = Button::new(cx, "reset");
+        if reset.was_clicked() {
+            config.strength = None;
+            config.shoot_time = None;
+            config.aim = CannonAim::Fixed;
+        }
+        column![
+            row![
+                strength_down.uniform_padding(8.0),
+                strength_up.uniform_padding(8.0),
+            ],
+            row![
+                delay_down.uniform_padding(8.0),
+                delay_up.uniform_padding(8.0),
+            ],
+            row![aim.uniform_padding(8.0), reset.uniform_padding(8.0)],
+            row![
+                sweep_down.uniform_padding(8.0),
+                sweep_up.uniform_padding(8.0),
+            ],
+            row![rate_down.uniform_padding(8.0), rate_up.uniform_padding(8.0)],
+        ]
+        .align(vec2(0.0, 0.0))
+        .boxed()
+    }
+}
+
+fn aim_name(aim: &CannonAim) -> &'static str {
+    match aim {
+        CannonAim::Fixed => "fixed aim",
+        CannonAim::Oscillate { .. } => "oscillating aim",
+        CannonAim::Player { .. } => "player aim",
     }
 }
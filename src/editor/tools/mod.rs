@@ -2,6 +2,7 @@
 
 use super::*;
 
+mod camera_zone;
 mod cannon;
 mod edit;
 mod endpoint;
@@ -10,7 +11,10 @@ mod portal;
 mod progress;
 mod surface;
 mod tile;
+mod trigger;
+mod wind_zone;
 
+pub use camera_zone::*;
 pub use cannon::*;
 pub use edit::*;
 pub use endpoint::*;
@@ -19,6 +23,8 @@ pub use portal::*;
 pub use progress::*;
 pub use surface::*;
 pub use tile::*;
+pub use trigger::*;
+pub use wind_zone::*;
 
 pub trait EditorTool: 'static {
     const NAME: &'static str;
@@ -39,6 +45,8 @@ pub trait EditorTool: 'static {
         level: &mut Level,
         selected_layer: usize,
     );
+    /// Gives the hovered element the settings a newly placed one would get
+    fn apply_config(&mut self, _cursor: &Cursor, _level: &mut Level, _selected_layer: usize) {}
     fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a>;
 }
 
@@ -62,6 +70,7 @@ pub trait DynEditorTool {
         level: &mut Level,
         selected_layer: usize,
     );
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, selected_layer: usize);
     fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a>;
 }
 
@@ -85,6 +94,9 @@ impl<T: EditorTool> DynEditorTool for T {
     ) {
         <T as EditorTool>::handle_event(self, cursor, event, level, selected_layer)
     }
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, selected_layer: usize) {
+        <T as EditorTool>::apply_config(self, cursor, level, selected_layer)
+    }
     fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
         <T as EditorTool>::ui(self, cx)
     }
@@ -2,12 +2,18 @@ use super::*;
 
 pub struct PortalToolConfig {
     snap_distance: f32,
+    /// Linking a portal also links the destination back
+    two_way: bool,
+    /// Facing step for the rotate keys
+    angle_step: f32,
 }
 
 impl EditorToolConfig for PortalToolConfig {
     fn default(assets: &AssetsHandle) -> Self {
         Self {
             snap_distance: assets.get().config.snap_distance,
+            two_way: false,
+            angle_step: f32::PI / 4.0,
         }
     }
 }
@@ -50,6 +56,19 @@ impl EditorTool for PortalTool {
         camera: &geng::Camera2d,
         framebuffer: &mut ugli::Framebuffer,
     ) {
+        for portal in &level.portals {
+            if let Some(angle) = portal.angle {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(
+                        Segment(portal.pos, portal.pos + vec2(1.0, 0.0).rotate(angle)),
+                        0.1,
+                        portal.color,
+                    ),
+                );
+            }
+        }
         if let Some(start) = self.start_drag {
             self.geng.draw2d().draw2d(
                 framebuffer,
@@ -92,6 +111,7 @@ impl EditorTool for PortalTool {
                         pos: cursor.world_pos,
                         dest: None,
                         color: random_hue(),
+                        angle: None,
                     });
                 }
             }
@@ -102,7 +122,11 @@ impl EditorTool for PortalTool {
                 if let Some(start) = self.start_drag.take() {
                     if let Some(index) = self.find_hovered_portal(cursor, level) {
                         if index != start {
-                            level.modify().portals[start].dest = Some(index);
+                            let level = level.modify();
+                            level.portals[start].dest = Some(index);
+                            if self.config.two_way {
+                                level.portals[index].dest = Some(start);
+                            }
                         }
                     }
                 }
@@ -125,19 +149,50 @@ impl EditorTool for PortalTool {
                     }
                 }
             }
-            geng::Event::KeyDown { key: geng::Key::C } => {
+            geng::Event::KeyDown { key: geng::Key::R } => {
+                if let Some(index) = self.find_hovered_portal(cursor, level) {
+                    let step = match self.geng.window().is_key_pressed(geng::Key::LShift) {
+                        true => -self.config.angle_step,
+                        false => self.config.angle_step,
+                    };
+                    let portal = &mut level.modify().portals[index];
+                    portal.angle = Some(portal.angle.map_or(0.0, |angle| angle + step));
+                }
+            }
+            geng::Event::KeyDown { key: geng::Key::F } => {
                 if let Some(index) = self.find_hovered_portal(cursor, level) {
-                    level.modify().portals[index].color = random_hue();
+                    let portal = &mut level.modify().portals[index];
+                    portal.angle = match portal.angle {
+                        Some(_) => None,
+                        None => Some(0.0),
+                    };
                 }
             }
             _ => {}
         }
     }
 
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, _selected_layer: usize) {
+        if let Some(index) = self.find_hovered_portal(cursor, level) {
+            level.modify().portals[index].color = random_hue();
+        }
+    }
+
     const NAME: &'static str = "Portal";
 
-    fn ui<'a>(&'a mut self, _cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
+    fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
         use geng::ui::*;
-        geng::ui::Void.fixed_size(vec2::ZERO).boxed()
+
+        let linking = Button::new(
+            cx,
+            match self.config.two_way {
+                true => "two-way",
+                false => "one-way",
+            },
+        );
+        if linking.was_clicked() {
+            self.config.two_way = !self.config.two_way;
+        }
+        linking.uniform_padding(8.0).align(vec2(0.0, 0.0)).boxed()
     }
 }
@@ -148,6 +148,8 @@ impl EditorTool for SurfaceTool {
                             p2,
                             flow: 0.0,
                             type_name: self.config.selected_type.clone(),
+                            group: None,
+                            enabled: true,
                         });
                 }
             }
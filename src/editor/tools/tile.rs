@@ -176,6 +176,8 @@ impl EditorTool for TileTool {
                         vertices,
                         flow: vec2::ZERO,
                         type_name: self.config.selected_type.clone(),
+                        group: None,
+                        enabled: true,
                     });
                 }
             }
@@ -0,0 +1,292 @@
+use super::*;
+
+pub struct TriggerToolConfig {
+    snap_distance: f32,
+    kind: TriggerKind,
+    group: char,
+    duration: Option<f32>,
+}
+
+impl EditorToolConfig for TriggerToolConfig {
+    fn default(assets: &AssetsHandle) -> Self {
+        Self {
+            snap_distance: assets.get().config.snap_distance,
+            kind: TriggerKind::PressurePlate,
+            group: 'A',
+            duration: None,
+        }
+    }
+}
+
+/// Surface or tile on the selected layer
+enum Element {
+    Surface(usize),
+    Tile(usize),
+}
+
+pub struct TriggerTool {
+    geng: Geng,
+    assets: AssetsHandle,
+    config: TriggerToolConfig,
+}
+
+impl TriggerTool {
+    fn group(&self) -> String {
+        self.config.group.to_string()
+    }
+
+    fn find_hovered_trigger(&self, cursor: &Cursor, level: &Level) -> Option<usize> {
+        level
+            .triggers
+            .iter()
+            .enumerate()
+            .filter(|(_index, trigger)| {
+                (trigger.pos - cursor.world_pos).len() < self.config.snap_distance
+            })
+            .min_by_key(|(_index, trigger)| r32((trigger.pos - cursor.world_pos).len()))
+            .map(|(index, _trigger)| index)
+    }
+
+    fn find_hovered_element(
+        &self,
+        cursor: &Cursor,
+        level: &Level,
+        selected_layer: usize,
+    ) -> Option<Element> {
+        let layer = &level.layers[selected_layer];
+        layer
+            .surfaces
+            .iter()
+            .enumerate()
+            .filter(|(_index, surface)| {
+                surface.vector_from(cursor.world_pos).len() < self.config.snap_distance
+            })
+            .min_by_key(|(_index, surface)| r32(surface.vector_from(cursor.world_pos).len()))
+            .map(|(index, _surface)| Element::Surface(index))
+            .or_else(|| {
+                layer
+                    .tiles
+                    .iter()
+                    .position(|tile| tile.contains(cursor.world_pos))
+                    .map(Element::Tile)
+            })
+    }
+}
+
+impl EditorTool for TriggerTool {
+    type Config = TriggerToolConfig;
+    fn new(geng: &Geng, assets: &AssetsHandle, config: TriggerToolConfig) -> Self {
+        Self {
+            geng: geng.clone(),
+            assets: assets.clone(),
+            config,
+        }
+    }
+    fn draw(
+        &self,
+        cursor: &Cursor,
+        level: &Level,
+        selected_layer: usize,
+        camera: &geng::Camera2d,
+        framebuffer: &mut ugli::Framebuffer,
+    ) {
+        // Elements of the current group, faded when they start disabled
+        let group = self.group();
+        let color = |enabled: bool| Rgba::new(1.0, 1.0, 0.0, if enabled { 0.8 } else { 0.3 });
+        let layer = &level.layers[selected_layer];
+        for surface in &layer.surfaces {
+            if surface.group.as_ref() == Some(&group) {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(
+                        Segment(surface.p1, surface.p2),
+                        0.2,
+                        color(surface.enabled),
+                    ),
+                );
+            }
+        }
+        for tile in &layer.tiles {
+            if tile.group.as_ref() == Some(&group) {
+                for i in 0..3 {
+                    self.geng.draw2d().draw2d(
+                        framebuffer,
+                        camera,
+                        &draw2d::Segment::new(
+                            Segment(tile.vertices[i], tile.vertices[(i + 1) % 3]),
+                            0.1,
+                            color(tile.enabled),
+                        ),
+                    );
+                }
+            }
+        }
+
+        let assets = self.assets.get();
+        let font = &assets.font;
+        for trigger in &level.triggers {
+            font.draw(
+                framebuffer,
+                camera,
+                &trigger.group,
+                vec2::splat(geng::TextAlign::CENTER),
+                mat3::translate(trigger.pos + vec2(0.0, 0.8)) * mat3::scale_uniform(0.4),
+                trigger.color,
+            );
+        }
+        if let Some(index) = self.find_hovered_trigger(cursor, level) {
+            let trigger = &level.triggers[index];
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                camera,
+                &draw2d::Quad::new(
+                    Aabb2::point(trigger.pos).extend_uniform(0.5),
+                    Rgba::new(1.0, 0.0, 0.0, 0.5),
+                ),
+            );
+        }
+        let kind = match self.config.kind {
+            TriggerKind::PressurePlate => "plate",
+            TriggerKind::Pickup => "pickup",
+        };
+        let timer = match self.config.duration {
+            Some(duration) => format!("{duration:.1}s"),
+            None => "no timer".to_owned(),
+        };
+        font.draw(
+            framebuffer,
+            camera,
+            &format!("{kind} {group} {timer}"),
+            vec2::splat(geng::TextAlign::LEFT),
+            mat3::translate(cursor.world_pos + vec2(0.5, -0.5)) * mat3::scale_uniform(0.4),
+            Rgba::new(0.0, 0.0, 1.0, 0.5),
+        );
+    }
+    fn handle_event(
+        &mut self,
+        cursor: &Cursor,
+        event: &geng::Event,
+        level: &mut Level,
+        selected_layer: usize,
+    ) {
+        match event {
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Left,
+                ..
+            } => {
+                level.modify().triggers.push(Trigger {
+                    pos: cursor.world_pos,
+                    kind: self.config.kind,
+                    group: self.group(),
+                    duration: self.config.duration,
+                    color: random_hue(),
+                });
+            }
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Right,
+                ..
+            } => {
+                if let Some(index) = self.find_hovered_trigger(cursor, level) {
+                    level.modify().triggers.remove(index);
+                }
+            }
+            geng::Event::KeyDown { key: geng::Key::G } => {
+                let group = self.group();
+                let toggle = |current: &mut Option<String>| {
+                    *current = match current.as_deref() {
+                        Some(existing) if existing == group => None,
+                        _ => Some(group.clone()),
+                    };
+                };
+                match self.find_hovered_element(cursor, level, selected_layer) {
+                    Some(Element::Surface(index)) => {
+                        toggle(&mut level.modify().layers[selected_layer].surfaces[index].group)
+                    }
+                    Some(Element::Tile(index)) => {
+                        toggle(&mut level.modify().layers[selected_layer].tiles[index].group)
+                    }
+                    None => {}
+                }
+            }
+            geng::Event::KeyDown { key: geng::Key::E } => {
+                match self.find_hovered_element(cursor, level, selected_layer) {
+                    Some(Element::Surface(index)) => {
+                        let surface = &mut level.modify().layers[selected_layer].surfaces[index];
+                        surface.enabled = !surface.enabled;
+                    }
+                    Some(Element::Tile(index)) => {
+                        let tile = &mut level.modify().layers[selected_layer].tiles[index];
+                        tile.enabled = !tile.enabled;
+                    }
+                    None => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, _selected_layer: usize) {
+        if let Some(index) = self.find_hovered_trigger(cursor, level) {
+            let group = self.group();
+            let trigger = &mut level.modify().triggers[index];
+            trigger.kind = self.config.kind;
+            trigger.group = group;
+            trigger.duration = self.config.duration;
+        }
+    }
+
+    const NAME: &'static str = "Trigger";
+
+    fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
+        use geng::ui::*;
+
+        let config = &mut self.config;
+        let kind = Button::new(
+            cx,
+            match config.kind {
+                TriggerKind::PressurePlate => "pressure plate",
+                TriggerKind::Pickup => "pickup",
+            },
+        );
+        if kind.was_clicked() {
+            config.kind = match config.kind {
+                TriggerKind::PressurePlate => TriggerKind::Pickup,
+                TriggerKind::Pickup => TriggerKind::PressurePlate,
+            };
+        }
+        let group_down = Button::new(cx, "group -");
+        if group_down.was_clicked() && config.group > 'A' {
+            config.group = (config.group as u8 - 1) as char;
+        }
+        let group_up = Button::new(cx, "group +");
+        if group_up.was_clicked() && config.group < 'Z' {
+            config.group = (config.group as u8 + 1) as char;
+        }
+        let timer_down = Button::new(cx, "timer -");
+        if timer_down.was_clicked() {
+            // Going below a second removes the timer
+            config.duration = config
+                .duration
+                .map(|duration| duration - 1.0)
+                .filter(|&duration| duration > 0.0);
+        }
+        let timer_up = Button::new(cx, "timer +");
+        if timer_up.was_clicked() {
+            config.duration = Some(config.duration.unwrap_or(0.0) + 1.0);
+        }
+        column![
+            kind.uniform_padding(8.0),
+            row![
+                group_down.uniform_padding(8.0),
+                group_up.uniform_padding(8.0),
+            ],
+            row![
+                timer_down.uniform_padding(8.0),
+                timer_up.uniform_padding(8.0),
+            ],
+        ]
+        .align(vec2(0.0, 0.0))
+        .boxed()
+    }
+}
@@ -0,0 +1,223 @@
+use super::*;
+
+pub struct WindZoneToolConfig {
+    snap_distance: f32,
+    strength: f32,
+    turbulence: f32,
+    frequency: f32,
+}
+
+impl EditorToolConfig for WindZoneToolConfig {
+    fn default(assets: &AssetsHandle) -> Self {
+        Self {
+            snap_distance: assets.get().config.snap_distance,
+            strength: 5.0,
+            turbulence: 0.3,
+            frequency: 0.5,
+        }
+    }
+}
+
+pub struct WindZoneTool {
+    geng: Geng,
+    assets: AssetsHandle,
+    drag_start: Option<vec2<f32>>,
+    config: WindZoneToolConfig,
+}
+
+impl WindZoneTool {
+    fn find_hovered_zone(&self, cursor: &Cursor, level: &Level) -> Option<usize> {
+        level
+            .wind_zones
+            .iter()
+            .enumerate()
+            .filter(|(_index, zone)| zone.rect.contains(cursor.world_pos))
+            .min_by_key(|(_index, zone)| r32(zone.rect.width() * zone.rect.height()))
+            .map(|(index, _zone)| index)
+    }
+}
+
+impl EditorTool for WindZoneTool {
+    type Config = WindZoneToolConfig;
+    fn new(geng: &Geng, assets: &AssetsHandle, config: WindZoneToolConfig) -> Self {
+        Self {
+            geng: geng.clone(),
+            assets: assets.clone(),
+            drag_start: None,
+            config,
+        }
+    }
+    fn draw(
+        &self,
+        cursor: &Cursor,
+        level: &Level,
+        _selected_layer: usize,
+        camera: &geng::Camera2d,
+        framebuffer: &mut ugli::Framebuffer,
+    ) {
+        let hovered = self.find_hovered_zone(cursor, level);
+        let color = Rgba::new(0.0, 0.5, 1.0, 0.5);
+        for (index, zone) in level.wind_zones.iter().enumerate() {
+            let corners = [
+                zone.rect.bottom_left(),
+                zone.rect.bottom_right(),
+                zone.rect.top_right(),
+                zone.rect.top_left(),
+            ];
+            for i in 0..4 {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Segment::new(Segment(corners[i], corners[(i + 1) % 4]), 0.05, color),
+                );
+            }
+            if hovered == Some(index) {
+                self.geng.draw2d().draw2d(
+                    framebuffer,
+                    camera,
+                    &draw2d::Quad::new(zone.rect, Rgba { a: 0.1, ..color }),
+                );
+            }
+            // Average wind, a second of movement long
+            let center = zone.rect.center();
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                camera,
+                &draw2d::Segment::new(Segment(center, center + zone.wind), 0.1, color),
+            );
+            self.assets.get().font.draw(
+                framebuffer,
+                camera,
+                &format!(
+                    "wind {:.1} turbulence {:.1} gusts {:.1}",
+                    zone.wind.len(),
+                    zone.turbulence,
+                    zone.frequency,
+                ),
+                vec2::splat(geng::TextAlign::LEFT),
+                mat3::translate(zone.rect.top_left() + vec2(0.1, -0.5)) * mat3::scale_uniform(0.4),
+                color,
+            );
+        }
+        if let Some(start) = self.drag_start {
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                camera,
+                &draw2d::Quad::new(
+                    Aabb2::from_corners(start, cursor.world_pos),
+                    Rgba::new(1.0, 0.0, 0.0, 0.2),
+                ),
+            );
+        }
+    }
+    fn handle_event(
+        &mut self,
+        cursor: &Cursor,
+        event: &geng::Event,
+        level: &mut Level,
+        _selected_layer: usize,
+    ) {
+        match event {
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Left,
+                ..
+            } => {
+                self.drag_start = Some(cursor.world_pos);
+            }
+            geng::Event::MouseUp {
+                button: geng::MouseButton::Left,
+                ..
+            } => {
+                if let Some(start) = self.drag_start.take() {
+                    let rect = Aabb2::from_corners(start, cursor.world_pos);
+                    if rect.width() > self.config.snap_distance
+                        && rect.height() > self.config.snap_distance
+                    {
+                        level.modify().wind_zones.push(WindZone {
+                            rect,
+                            wind: vec2(self.config.strength, 0.0),
+                            turbulence: self.config.turbulence,
+                            frequency: self.config.frequency,
+                        });
+                    }
+                }
+            }
+            geng::Event::MouseDown {
+                button: geng::MouseButton::Right,
+                ..
+            } => {
+                if let Some(index) = self.find_hovered_zone(cursor, level) {
+                    level.modify().wind_zones.remove(index);
+                }
+            }
+            geng::Event::KeyDown { key: geng::Key::F } => {
+                // Point the wind towards the cursor
+                if let Some(index) = self.find_hovered_zone(cursor, level) {
+                    let zone = &mut level.modify().wind_zones[index];
+                    let direction = (cursor.world_pos - zone.rect.center()).normalize_or_zero();
+                    if direction != vec2::ZERO {
+                        zone.wind = direction * zone.wind.len();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_config(&mut self, cursor: &Cursor, level: &mut Level, _selected_layer: usize) {
+        if let Some(index) = self.find_hovered_zone(cursor, level) {
+            let zone = &mut level.modify().wind_zones[index];
+            zone.wind = zone.wind.normalize_or_zero() * self.config.strength;
+            zone.turbulence = self.config.turbulence;
+            zone.frequency = self.config.frequency;
+        }
+    }
+
+    const NAME: &'static str = "Wind";
+
+    fn ui<'a>(&'a mut self, cx: &'a geng::ui::Controller) -> Box<dyn geng::ui::Widget + 'a> {
+        use geng::ui::*;
+
+        let config = &mut self.config;
+        let strength_down = Button::new(cx, "wind -");
+        if strength_down.was_clicked() {
+            config.strength = (config.strength - 1.0).max(1.0);
+        }
+        let strength_up = Button::new(cx, "wind +");
+        if strength_up.was_clicked() {
+            config.strength += 1.0;
+        }
+        let turbulence_down = Button::new(cx, "turbulence -");
+        if turbulence_down.was_clicked() {
+            config.turbulence = (config.turbulence - 0.1).max(0.0);
+        }
+        let turbulence_up = Button::new(cx, "turbulence +");
+        if turbulence_up.was_clicked() {
+            config.turbulence += 0.1;
+        }
+        let frequency_down = Button::new(cx, "gusts -");
+        if frequency_down.was_clicked() {
+            config.frequency = (config.frequency - 0.1).max(0.1);
+        }
+        let frequency_up = Button::new(cx, "gusts +");
+        if frequency_up.was_clicked() {
+            config.frequency += 0.1;
+        }
+        column![
+            row![
+                strength_down.uniform_padding(8.0),
+                strength_up.uniform_padding(8.0),
+            ],
+            row![
+                turbulence_down.uniform_padding(8.0),
+                turbulence_up.uniform_padding(8.0),
+            ],
+            row![
+                frequency_down.uniform_padding(8.0),
+                frequency_up.uniform_padding(8.0),
+            ],
+        ]
+        .align(vec2(0.0, 0.0))
+        .boxed()
+    }
+}
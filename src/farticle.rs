@@ -13,14 +13,32 @@ pub struct Farticle {
 impl Game {
     pub fn update_farticles(&mut self, delta_time: f32) {
         let assets = self.assets.get();
+        let platform_surfaces: Vec<Surface> = self
+            .level
+            .platform_surfaces(self.level_time)
+            .map(|(_index, surface)| surface)
+            .collect();
         for (type_name, farticles) in &mut self.farticles {
             let fart_assets = &assets.farts[type_name];
             for farticle in &mut *farticles {
                 farticle.t -= delta_time / fart_assets.config.farticle_lifetime;
+                if let Some(wind) =
+                    self.level
+                        .wind_at(farticle.pos, self.simulation_time, &self.noise)
+                {
+                    farticle.vel += (wind - farticle.vel)
+                        * (assets.config.wind.farticle_drag * delta_time).min(1.0);
+                }
                 farticle.pos += farticle.vel * delta_time;
                 farticle.rot += farticle.w * delta_time;
 
-                for surface in self.level.gameplay_surfaces() {
+                let area = Aabb2::point(farticle.pos)
+                    .extend_uniform(fart_assets.config.farticle_size / 2.0);
+                for surface in self
+                    .level
+                    .gameplay_surfaces_near(area)
+                    .chain(&platform_surfaces)
+                {
                     let v = surface.vector_from(farticle.pos);
                     let penetration = fart_assets.config.farticle_size / 2.0 - v.len();
                     if penetration > EPS && vec2::dot(v, farticle.vel) > 0.0 {
@@ -1,9 +1,5 @@
 use super::*;
 
-pub const CONTROLS_LEFT: [geng::Key; 2] = [geng::Key::A, geng::Key::Left];
-pub const CONTROLS_RIGHT: [geng::Key; 2] = [geng::Key::D, geng::Key::Right];
-pub const CONTROLS_FORCE_FART: [geng::Key; 3] = [geng::Key::W, geng::Key::Up, geng::Key::Space];
-
 pub struct LongFartSfx {
     pub finish_time: Option<f32>,
     pub type_name: String,
@@ -20,37 +16,59 @@ pub struct Game {
     pub config: Rc<Config>,
     pub assets: AssetsHandle,
     pub camera: geng::Camera2d,
+    pub camera_director: CameraDirector,
+    /// Layer parallax multiplier for the camera being drawn
+    pub camera_parallax: f32,
     pub level: Level,
     pub editor: Option<EditorState>,
     pub guys: Collection<Guy>,
     pub my_guy: Option<Id>,
     pub simulation_time: f32,
+    /// Time since the level was opened, keeps running through restarts, pauses and finishes
+    /// so moving parts of the level stay in sync for every guy
+    pub level_time: f32,
     pub remote_updates: HashMap<Id, Replay>,
     pub real_time: f32,
     pub noise: noise::OpenSimplex,
     pub opt: Opt,
     pub farticles: HashMap<String, Vec<Farticle>>,
-    pub volume: f32,
+    pub wind_streaks: Vec<WindStreak>,
+    pub settings: Settings,
+    pub bindings: Bindings,
+    /// Action waiting for an input to be bound to
+    pub rebinding: Option<Action>,
     pub client_id: Id,
     pub connection: Option<Connection>,
     pub customization: CustomizationOptions,
-    pub mute_music: bool,
     pub ui_controller: ui::Controller,
+    pub name_input: ui::TextInput,
+    pub touch_controls: TouchControls,
+    pub assists: Assists,
+    pub local_players: Vec<LocalPlayer>,
+    pub split_screen_textures: Vec<ugli::Texture>,
     pub buttons: Vec<ui::Button<UiMessage>>,
     pub show_customizer: bool,
     pub music: geng::SoundEffect,
-    pub show_names: bool,
-    pub show_leaderboard: bool,
     pub follow: Option<Id>,
     pub long_fart_sfx: HashMap<Id, LongFartSfx>,
     pub next_golden_glint: f32,
     pub time_scale: f32,
-    pub quicksave: Option<Guy>,
+    pub savestates: Vec<Option<Savestate>>,
+    pub selected_savestate: usize,
     pub replays: Vec<Replay>,
     pub recording: Option<Replay>,
     pub video_editor: Option<video_editor::VideoEditor>,
+    /// Gamepad of the first player, other gamepads join as local players
     pub active_gamepad: Option<gilrs::GamepadId>,
+    /// The first player pressed a key before any gamepad button, so gamepads only join
+    pub keyboard_player_one: bool,
     pub next_save: f32,
+    pub level_identity: u64,
+    pub save_slots: Vec<SaveSlot>,
+    /// Slot that autosave writes to, none after deleting it until a slot is picked
+    pub current_save: Option<String>,
+    pub show_load_menu: bool,
+    pub pause_menu: Option<PauseScreen>,
 }
 
 impl Drop for Game {
@@ -73,6 +91,9 @@ impl Game {
             Some((client_id, connection)) => (client_id, Some(connection)),
             None => (Id::LOCALHOST, None),
         };
+        let level_identity = level.identity();
+        let settings = Settings::load(&assets.get().config);
+        let time_scale = settings.game_speed;
         let mut result = Self {
             best_time: None,
             emotes: vec![],
@@ -82,8 +103,10 @@ impl Game {
             camera: geng::Camera2d {
                 center: level.spawn_point,
                 rotation: 0.0,
-                fov: assets.get().config.camera_fov,
+                fov: settings.camera_fov,
             },
+            camera_director: default(),
+            camera_parallax: 1.0,
             framebuffer_size: vec2(1.0, 1.0),
             editor: if opt.editor {
                 Some(EditorState::new(geng, assets))
@@ -98,16 +121,28 @@ impl Game {
             prev_mouse_pos: vec2::ZERO,
             opt: opt.clone(),
             farticles: default(),
-            volume: assets.get().config.volume,
+            wind_streaks: vec![],
+            settings,
+            bindings: Bindings::load(assets.get().emotes.len()),
+            rebinding: None,
             client_id,
             connection,
-            simulation_time: preferences::load("simulation_time").unwrap_or(0.0),
+            simulation_time: 0.0,
+            level_time: 0.0,
             remote_updates: default(),
             customization: preferences::load("customization")
                 .unwrap_or_else(CustomizationOptions::random),
-            mute_music: false,
             best_progress: 0.0,
             ui_controller: ui::Controller::new(geng, assets),
+            name_input: ui::TextInput::new(
+                geng,
+                CustomizationOptions::MAX_NAME_LEN,
+                CustomizationOptions::is_valid_name_char,
+            ),
+            touch_controls: default(),
+            assists: default(),
+            local_players: vec![],
+            split_screen_textures: vec![],
             buttons: vec![
                 ui::Button::new("PLAY", vec2(0.0, -3.0), 1.0, 0.5, UiMessage::Play),
                 ui::Button::new(
@@ -117,6 +152,7 @@ impl Game {
                     0.0,
                     UiMessage::RandomizeSkin,
                 ),
+                ui::Button::new("load", vec2(2.0, -1.0), 0.7, 0.0, UiMessage::OpenLoadMenu),
             ],
             show_customizer: !opt.editor,
             music: {
@@ -124,13 +160,12 @@ impl Game {
                 effect.set_volume(0.0);
                 effect
             },
-            show_names: true,
-            show_leaderboard: true,
             follow: None,
             long_fart_sfx: HashMap::new(),
             next_golden_glint: 0.0,
-            quicksave: None,
-            time_scale: 1.0,
+            savestates: vec![None; SAVESTATE_SLOTS],
+            selected_savestate: 0,
+            time_scale,
             replays: if cfg!(target_arch = "wasm32") {
                 vec![]
             } else {
@@ -151,15 +186,27 @@ impl Game {
                 .as_ref()
                 .map(|path| video_editor::VideoEditor::new(geng, path)),
             active_gamepad: None,
+            keyboard_player_one: false,
             next_save: 0.0,
+            level_identity,
+            save_slots: load_save_slots(level_identity),
+            current_save: Some(
+                preferences::load("current_save").unwrap_or_else(|| "save 1".to_owned()),
+            ),
+            show_load_menu: false,
+            pause_menu: None,
         };
         if !opt.editor {
             result.my_guy = Some(client_id);
-            let mut me = Guy::new(client_id, result.level.spawn_point, true, &result.config);
-            if let Some(state) = preferences::load("save") {
-                me.state = state;
-            }
+            let me = Guy::new(client_id, result.level.spawn_point, true, &result.config);
             result.guys.insert(me);
+            if let Some(index) = result
+                .save_slots
+                .iter()
+                .position(|slot| Some(&slot.name) == result.current_save.as_ref())
+            {
+                result.load_save(index);
+            }
         }
         result
     }
@@ -197,7 +244,10 @@ impl Game {
             guy.progress.current = progress;
             self.best_progress = self.best_progress.max(progress);
             guy.progress.best = self.best_progress;
-            if guy.progress.finished && self.simulation_time < self.best_time.unwrap_or(1e9) {
+            if guy.progress.finished
+                && !guy.progress.used_savestates
+                && self.simulation_time < self.best_time.unwrap_or(1e9)
+            {
                 self.best_time = Some(self.simulation_time);
             }
             guy.progress.best_time = self.best_time;
@@ -259,25 +309,48 @@ impl Game {
             }
         }
     }
-    fn save_replays(&self) {
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            replay::save(
-                run_dir().join("replays"),
-                &self
-                    .replays
-                    .iter()
-                    .map(|replay| &replay.history)
-                    .collect::<Vec<_>>(),
-            )
-            .unwrap();
+
+    /// Time left on a timed fart type power-up
+    fn draw_fart_type_timer(&self, framebuffer: &mut ugli::Framebuffer) {
+        if self.show_customizer {
+            return;
         }
+        let Some(guy) = self.my_guy.and_then(|id| self.guys.get(&id)) else { return };
+        let Some(timer) = &guy.state.fart_type_timer else { return };
+        let camera = geng::Camera2d {
+            center: vec2::ZERO,
+            rotation: 0.0,
+            fov: 10.0,
+        };
+        self.assets.get().font.draw(
+            framebuffer,
+            &camera,
+            &format!("{} {:.1}", guy.state.fart_type, timer.time_left.max(0.0)),
+            vec2::splat(geng::TextAlign::CENTER),
+            mat3::translate(vec2(0.0, 4.0)) * mat3::scale_uniform(0.5),
+            Rgba::BLACK,
+        );
+        let fraction = (timer.time_left / timer.duration).clamp(0.0, 1.0);
+        self.geng.draw2d().draw2d(
+            framebuffer,
+            &camera,
+            &draw2d::Quad::new(
+                Aabb2::point(vec2(0.0, 3.6)).extend_symmetric(vec2(2.0, 0.1)),
+                Rgba::new(0.0, 0.0, 0.0, 0.5),
+            ),
+        );
+        self.geng.draw2d().draw2d(
+            framebuffer,
+            &camera,
+            &draw2d::Quad::new(
+                Aabb2::from_corners(vec2(-2.0, 3.5), vec2(-2.0 + 4.0 * fraction, 3.7)),
+                Rgba::BLACK,
+            ),
+        );
     }
-}
 
-impl geng::State for Game {
-    fn draw(&mut self, framebuffer: &mut ugli::Framebuffer) {
-        self.framebuffer_size = framebuffer.size().map(|x| x as f32);
+    /// Draws the level and everything in it using the current camera
+    pub fn draw_world(&mut self, framebuffer: &mut ugli::Framebuffer) {
         let finished = self
             .my_guy
             .and_then(|id| self.guys.get(&id))
@@ -313,15 +386,47 @@ impl geng::State for Game {
                 );
                 self.draw_guys(framebuffer);
                 self.draw_farticles(framebuffer);
+                self.draw_wind_streaks(framebuffer);
             }
             if !finished {
                 self.draw_layer_front(&self.level, index, framebuffer);
             }
         }
+    }
+
+    fn save_replays(&self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            replay::save(
+                run_dir().join("replays"),
+                &self
+                    .replays
+                    .iter()
+                    .map(|replay| &replay.history)
+                    .collect::<Vec<_>>(),
+            )
+            .unwrap();
+        }
+    }
+}
+
+impl geng::State for Game {
+    fn draw(&mut self, framebuffer: &mut ugli::Framebuffer) {
+        self.framebuffer_size = framebuffer.size().map(|x| x as f32);
+        if self.split_screen() {
+            self.draw_split_screen(framebuffer);
+        } else {
+            self.draw_world(framebuffer);
+        }
         self.draw_level_editor(framebuffer);
         self.draw_customizer(framebuffer);
         self.draw_leaderboard(framebuffer);
         self.draw_progress(framebuffer);
+        self.draw_fart_type_timer(framebuffer);
+        self.draw_practice(framebuffer);
+        self.draw_touch_controls(framebuffer);
+        self.draw_assists(framebuffer);
+        self.draw_pause_menu(framebuffer);
 
         if self.recording.is_some() {
             self.geng.default_font().draw(
@@ -334,7 +439,7 @@ impl geng::State for Game {
             );
         }
 
-        if let Some(radius) = self.opt.accessibility {
+        if let Some(radius) = self.accessibility_radius() {
             let center = framebuffer.size().map(|x| x as f32) / 2.0;
             self.geng.draw2d().draw2d(
                 framebuffer,
@@ -359,14 +464,18 @@ impl geng::State for Game {
 
     fn fixed_update(&mut self, delta_time: f64) {
         let delta_time = delta_time as f32 * self.time_scale;
+        self.level_time += delta_time;
         if let Some(me) = self.my_guy.and_then(|id| self.guys.get(&id)) {
             if !me.progress.finished && !me.paused {
                 self.simulation_time += delta_time;
             }
         }
+        self.update_local_players_time(delta_time);
         self.update_my_guy_input();
+        self.update_local_players_input();
         self.update_guys(delta_time);
         self.update_farticles(delta_time);
+        self.update_wind_streaks(delta_time);
         self.update_remote(delta_time);
         self.update_replays(delta_time);
     }
@@ -377,48 +486,34 @@ impl geng::State for Game {
         self.next_save -= delta_time;
         if self.next_save < 0.0 {
             self.next_save = 1.0;
-            if let Some(me) = self.my_guy.and_then(|id| self.guys.get(&id)) {
-                preferences::save("save", &me.state);
-                preferences::save("simulation_time", &self.simulation_time);
+            if self.editor.is_none() {
+                self.write_save();
             }
         }
 
-        // self.volume = self.assets.config.volume;
         if self.geng.window().is_key_pressed(geng::Key::PageUp) {
-            self.volume += delta_time * 0.5;
+            self.settings.master_volume += delta_time * 0.5;
         }
         if self.geng.window().is_key_pressed(geng::Key::PageDown) {
-            self.volume -= delta_time * 0.5;
-        }
-        self.volume = self.volume.clamp(0.0, 1.0);
-        if self.mute_music {
-            self.music.set_volume(0.0);
-        } else {
-            self.music.set_volume(self.volume as f64);
+            self.settings.master_volume -= delta_time * 0.5;
         }
+        self.settings.master_volume = self.settings.master_volume.clamp(0.0, 1.0);
+        self.music.set_volume(self.settings.music_volume() as f64);
 
         self.emotes.retain(|&(t, ..)| t >= self.real_time - 1.0);
 
         self.real_time += delta_time;
 
-        let mut target_center = self.camera.center;
-        if let Some(id) = self.my_guy {
-            let guy = self.guys.get(&id).unwrap();
-            target_center = guy.state.pos;
-            if self.show_customizer {
-                target_center.x += 1.0;
-            }
-        } else if let Some(id) = self.follow {
-            if let Some(guy) = self.guys.get(&id) {
-                target_center = guy.state.pos;
-            }
-        }
-        self.camera.center += (target_center - self.camera.center) * (delta_time * 5.0).min(1.0);
-
-        if self.editor.is_none() {
-            // let target_fov = if self.show_customizer { 2.0 } else { 6.0 };
-            // self.camera.fov += (target_fov - self.camera.fov) * delta_time;
+        let mut targets: Vec<Id> = self.my_guy.or(self.follow).into_iter().collect();
+        if !self.split_screen() {
+            targets.extend(self.local_players.iter().map(|player| player.id));
         }
+        let mut camera = self.camera.clone();
+        let mut director = mem::take(&mut self.camera_director);
+        self.direct_camera(&mut director, &mut camera, &targets, delta_time);
+        self.camera = camera;
+        self.camera_parallax = director.parallax;
+        self.camera_director = director;
 
         if let Some(editor) = &mut self.editor {
             editor.update(&mut self.level, delta_time);
@@ -426,6 +521,13 @@ impl geng::State for Game {
 
         self.handle_connection();
 
+        self.update_assists(delta_time);
+        self.update_local_players(delta_time);
+
+        self.name_input.set_editing(
+            &self.customization.name,
+            self.show_customizer && !self.show_load_menu,
+        );
         if let Some(id) = self.my_guy {
             let guy = self.guys.get_mut(&id).unwrap();
             guy.customization.name = self.customization.name.clone();
@@ -455,12 +557,61 @@ impl geng::State for Game {
     }
 
     fn handle_event(&mut self, event: geng::Event) {
+        self.handle_touch_event(&event);
+        if self.handle_local_players_event(&event) {
+            return;
+        }
+        if self.pause_menu.is_some() {
+            self.handle_pause_menu_event(&event);
+            return;
+        }
         self.handle_event_editor(&event);
-        self.handle_customizer_event(&event);
-        match event {
-            geng::Event::Gamepad(event) => {
-                self.active_gamepad = Some(event.id);
+        if let geng::Event::KeyDown { .. } = event {
+            if self.active_gamepad.is_none() {
+                self.keyboard_player_one = true;
+            }
+        }
+        if self.show_load_menu {
+            self.handle_load_menu_event(&event);
+        } else {
+            self.handle_customizer_event(&event);
+        }
+        // Other gamepads only control their own local players
+        let other_gamepad = matches!(
+            &event,
+            geng::Event::Gamepad(pad) if self.active_gamepad.map_or(false, |id| id != pad.id)
+        );
+        let actions = if other_gamepad {
+            vec![]
+        } else {
+            self.bindings.triggered(&self.geng, &event)
+        };
+        for action in actions {
+            match action {
+                Action::Restart => self.respawn_my_guy(),
+                Action::Checkpoint if !self.show_customizer && self.editor.is_none() => {
+                    self.respawn_at_checkpoint();
+                }
+                Action::Emote(emote) if !self.show_customizer => {
+                    if let Some(con) = &mut self.connection {
+                        con.send(ClientMessage::Emote(emote));
+                    }
+                }
+                _ => {}
             }
+        }
+        match event {
+            geng::Event::Gamepad(event) => match event.event {
+                gilrs::EventType::ButtonPressed(..)
+                    if self.active_gamepad.is_none() && !self.keyboard_player_one =>
+                {
+                    self.active_gamepad = Some(event.id);
+                }
+                gilrs::EventType::Disconnected if self.active_gamepad == Some(event.id) => {
+                    self.active_gamepad = None;
+                }
+                _ => {}
+            },
             geng::Event::MouseMove { position, .. }
                 if self
                     .geng
@@ -501,47 +652,19 @@ impl geng::State for Game {
             geng::Event::Wheel { delta } if self.opt.editor => {
                 self.camera.fov = (self.camera.fov * 1.01f32.powf(-delta as f32)).clamp(1.0, 200.0);
             }
-            geng::Event::KeyDown { key: geng::Key::R }
-                if self.geng.window().is_key_pressed(geng::Key::LCtrl) =>
-            {
-                self.respawn_my_guy();
+            geng::Event::KeyUp {
+                key: geng::Key::PageUp | geng::Key::PageDown,
+            } => {
+                self.settings.save();
             }
             geng::Event::KeyDown { key: geng::Key::M } if !self.show_customizer => {
-                self.mute_music = !self.mute_music;
+                self.settings.adjust(SettingsItem::MuteMusic, 1, true);
             }
             geng::Event::KeyDown { key: geng::Key::H } if !self.show_customizer => {
-                self.show_names = !self.show_names;
+                self.settings.adjust(SettingsItem::ShowNames, 1, true);
             }
             geng::Event::KeyDown { key: geng::Key::L } if !self.show_customizer => {
-                self.show_leaderboard = !self.show_leaderboard;
-            }
-            geng::Event::KeyDown {
-                key: geng::Key::Num1,
-            } => {
-                if let Some(con) = &mut self.connection {
-                    con.send(ClientMessage::Emote(0));
-                }
-            }
-            geng::Event::KeyDown {
-                key: geng::Key::Num2,
-            } => {
-                if let Some(con) = &mut self.connection {
-                    con.send(ClientMessage::Emote(1));
-                }
-            }
-            geng::Event::KeyDown {
-                key: geng::Key::Num3,
-            } => {
-                if let Some(con) = &mut self.connection {
-                    con.send(ClientMessage::Emote(2));
-                }
-            }
-            geng::Event::KeyDown {
-                key: geng::Key::Num4,
-            } => {
-                if let Some(con) = &mut self.connection {
-                    con.send(ClientMessage::Emote(3));
-                }
+                self.settings.adjust(SettingsItem::ShowLeaderboard, 1, true);
             }
             geng::Event::KeyDown {
                 key: geng::Key::Tab,
@@ -551,17 +674,16 @@ impl geng::State for Game {
                 }
             }
             geng::Event::KeyDown { key: geng::Key::I } => {
-                self.camera.fov = self.assets.get().config.camera_fov;
+                self.camera.fov = self.settings.camera_fov;
             }
-            geng::Event::KeyDown { key: geng::Key::F5 } if self.opt.editor => {
-                self.quicksave = self.my_guy.and_then(|id| self.guys.get(&id)).cloned();
+            geng::Event::KeyDown { key: geng::Key::F5 } if self.practice_mode() => {
+                self.save_state();
             }
-            geng::Event::KeyDown { key: geng::Key::F7 } if self.opt.editor => {
-                if let Some(save) = &self.quicksave {
-                    let save = save.clone();
-                    self.respawn_my_guy();
-                    *self.guys.get_mut(&self.my_guy.unwrap()).unwrap() = save;
-                }
+            geng::Event::KeyDown { key: geng::Key::F6 } if self.practice_mode() => {
+                self.select_next_savestate();
+            }
+            geng::Event::KeyDown { key: geng::Key::F7 } if self.practice_mode() => {
+                self.load_state();
             }
             geng::Event::KeyDown { key: geng::Key::Z } if self.opt.editor => {
                 self.time_scale = 1.0;
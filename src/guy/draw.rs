@@ -187,7 +187,7 @@ impl Game {
                 }
             }
 
-            if Some(guy.id) == self.my_guy || self.show_names {
+            if Some(guy.id) == self.my_guy || self.settings.show_names {
                 assets.font.draw(
                     framebuffer,
                     &self.camera,
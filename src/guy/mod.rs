@@ -22,12 +22,28 @@ pub struct CustomizationOptions {
 }
 
 impl CustomizationOptions {
+    pub const MAX_NAME_LEN: usize = 15;
+
     pub fn random() -> Self {
         Self {
             name: "".to_owned(),
             colors: GuyColors::random(),
         }
     }
+
+    pub fn is_valid_name_char(c: char) -> bool {
+        !c.is_control()
+    }
+
+    /// Name as accepted by the server: only valid characters, trimmed and limited in length
+    pub fn sanitize_name(name: &str) -> String {
+        let name: String = name
+            .chars()
+            .filter(|&c| Self::is_valid_name_char(c))
+            .take(Self::MAX_NAME_LEN)
+            .collect();
+        name.trim().to_owned()
+    }
 }
 
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -36,6 +52,23 @@ pub struct Progress {
     pub current: f32,
     pub best: f32,
     pub best_time: Option<f32>,
+    #[serde(default)]
+    pub checkpoint: Option<vec2<f32>>,
+    /// Savestates were loaded during this run, so it does not count on the leaderboard
+    #[serde(default)]
+    pub used_savestates: bool,
+    /// Accessibility assists were enabled during this run
+    #[serde(default)]
+    pub assisted: bool,
+}
+
+impl Progress {
+    pub fn ranked_time(&self) -> Option<f32> {
+        if self.used_savestates {
+            return None;
+        }
+        self.best_time
+    }
 }
 
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
@@ -58,6 +91,10 @@ pub struct PhysicsState {
     pub cannon_timer: Option<CannonTimer>,
     pub stick_force: vec2<f32>,
     pub bubble_timer: Option<f32>,
+    #[serde(default)]
+    pub fart_type_timer: Option<FartTypeTimer>,
+    #[serde(default)]
+    pub portal_cooldown: Option<f32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, HasId)]
@@ -71,6 +108,15 @@ pub struct Guy {
     pub paused: bool,
 }
 
+/// Fart type from a timed power-up
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FartTypeTimer {
+    pub time_left: f32,
+    pub duration: f32,
+    /// Fart type to go back to once the time runs out
+    pub previous: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct CannonTimer {
     pub cannon_index: usize,
@@ -124,6 +170,8 @@ impl Guy {
                 cannon_timer: None,
                 stick_force: vec2::ZERO,
                 bubble_timer: None,
+                fart_type_timer: None,
+                portal_cooldown: None,
                 long_farting: false,
                 fart_pressure: 0.0,
             },
@@ -133,6 +181,9 @@ impl Guy {
                 current: 0.0,
                 best: 0.0,
                 best_time: None,
+                checkpoint: None,
+                used_savestates: false,
+                assisted: false,
             },
             animation: GuyAnimationState {
                 growl_progress: None,
@@ -8,6 +8,14 @@ impl Id {
     pub fn replay(index: usize) -> Self {
         Self(-(index as i32 + 2))
     }
+    /// Whether this is a ghost played back from a replay
+    pub fn is_replay(&self) -> bool {
+        self.0 <= -2 && self.0 >= i32::MIN + MAX_LOCAL_PLAYERS as i32
+    }
+    /// Ids for split-screen players, far away from the replay ids
+    pub fn local_player(index: usize) -> Self {
+        Self(i32::MIN + index as i32)
+    }
 }
 
 pub struct IdGen {
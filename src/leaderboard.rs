@@ -2,11 +2,11 @@ use super::*;
 
 impl Game {
     pub fn draw_leaderboard(&self, framebuffer: &mut ugli::Framebuffer) {
-        if !self.show_leaderboard {
+        if !self.settings.show_leaderboard {
             return;
         }
         let mut guys: Vec<&Guy> = self.guys.iter().collect();
-        guys.sort_by(|a, b| match (a.progress.best_time, b.progress.best_time) {
+        guys.sort_by(|a, b| match (a.progress.ranked_time(), b.progress.ranked_time()) {
             (Some(a), Some(b)) => a.partial_cmp(&b).unwrap(),
             (Some(_), None) => std::cmp::Ordering::Less,
             (None, Some(_)) => std::cmp::Ordering::Greater,
@@ -47,6 +47,12 @@ impl Game {
                 text += &format!("{}%", (guy.progress.best * 100.0).round() as i32);
             }
             text.push(')');
+            if guy.progress.used_savestates {
+                text += " [practice]";
+            }
+            if guy.progress.assisted {
+                text += " [assisted]";
+            }
             self.geng.default_font().draw(
                 framebuffer,
                 &camera,
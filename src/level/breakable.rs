@@ -0,0 +1,80 @@
+use super::*;
+
+/// Surface on a layer of the level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SurfaceId {
+    pub layer: usize,
+    pub index: usize,
+}
+
+/// Runtime state of a breakable surface, intact surfaces have none
+#[derive(Debug, Clone, Copy)]
+pub enum SurfaceState {
+    /// Touched, breaks at the given level time
+    Crumbling { break_time: f32 },
+    /// Gone until the given level time
+    Broken { respawn_time: f32 },
+}
+
+/// Contact with a breakable surface during a physics step
+pub struct SurfaceHit {
+    pub id: SurfaceId,
+    pub impulse: f32,
+}
+
+impl SurfaceParams {
+    pub fn breakable(&self) -> bool {
+        self.crumble_time.is_some() || self.break_impulse.is_some()
+    }
+}
+
+impl Level {
+    pub fn surface_state(&self, id: SurfaceId) -> Option<SurfaceState> {
+        self.surface_states.get(&id).copied()
+    }
+
+    pub fn is_broken(&self, id: SurfaceId) -> bool {
+        matches!(self.surface_state(id), Some(SurfaceState::Broken { .. }))
+    }
+
+    /// Applies the hits of a physics step and advances crumbling and broken surfaces.
+    /// Times are on the level clock, which keeps running when a guy restarts,
+    /// so a break stays until the surface respawns no matter who caused it
+    pub fn update_surface_states(&mut self, time: f32, hits: &[SurfaceHit], assets: &Assets) {
+        let params = |id: SurfaceId| {
+            let surface = &self.info.layers[id.layer].surfaces[id.index];
+            &assets.surfaces[&surface.type_name].params
+        };
+        for hit in hits {
+            let params = params(hit.id);
+            if params
+                .break_impulse
+                .map_or(false, |threshold| hit.impulse >= threshold)
+            {
+                self.surface_states.insert(
+                    hit.id,
+                    SurfaceState::Broken {
+                        respawn_time: time + params.respawn_time,
+                    },
+                );
+            } else if let Some(crumble_time) = params.crumble_time {
+                self.surface_states
+                    .entry(hit.id)
+                    .or_insert(SurfaceState::Crumbling {
+                        break_time: time + crumble_time,
+                    });
+            }
+        }
+        self.surface_states.retain(|&id, state| match *state {
+            SurfaceState::Crumbling { break_time } => {
+                if time >= break_time {
+                    *state = SurfaceState::Broken {
+                        respawn_time: break_time + params(id).respawn_time,
+                    };
+                }
+                true
+            }
+            SurfaceState::Broken { respawn_time } => time < respawn_time,
+        });
+    }
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+/// Level area where the camera uses its own settings
+#[derive(Serialize, Deserialize, Clone)]
+pub struct CameraZone {
+    pub rect: Aabb2<f32>,
+    pub fov: f32,
+    /// Camera stays at this point instead of following the guy
+    #[serde(default)]
+    pub center: Option<vec2<f32>>,
+    /// Multiplier for how much the layer parallax differs from the main layer
+    #[serde(default = "default_zone_parallax")]
+    pub parallax: f32,
+}
+
+fn default_zone_parallax() -> f32 {
+    1.0
+}
+
+impl CameraZone {
+    /// How far inside the zone the point is, from 0 at the border to 1 at `blend_distance`
+    pub fn weight(&self, pos: vec2<f32>, blend_distance: f32) -> f32 {
+        let depth = (pos.x - self.rect.min.x)
+            .min(self.rect.max.x - pos.x)
+            .min(pos.y - self.rect.min.y)
+            .min(self.rect.max.y - pos.y);
+        (depth / blend_distance).clamp(0.0, 1.0)
+    }
+}
+
+impl LevelInfo {
+    /// The innermost camera zone containing the point
+    pub fn camera_zone_at(&self, pos: vec2<f32>) -> Option<&CameraZone> {
+        self.camera_zones
+            .iter()
+            .filter(|zone| zone.rect.contains(pos))
+            .min_by(|a, b| {
+                let a = a.rect.width() * a.rect.height();
+                let b = b.rect.width() * b.rect.height();
+                a.partial_cmp(&b).unwrap()
+            })
+    }
+}
@@ -1,7 +1,44 @@
 use super::*;
 
+/// How a cannon aims while a guy is loaded
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CannonAim {
+    #[default]
+    Fixed,
+    /// Swings between `rot - amplitude` and `rot + amplitude`
+    Oscillate { amplitude: f32, period: f32 },
+    /// Turned by the roll input, at most `range` away from `rot`
+    Player { range: f32, speed: f32 },
+}
+
+impl CannonAim {
+    pub fn is_fixed(&self) -> bool {
+        *self == Self::Fixed
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Cannon {
     pub pos: vec2<f32>,
     pub rot: f32,
+    /// Overrides the shot strength from the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strength: Option<f32>,
+    /// Overrides the time before shooting from the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shoot_time: Option<f32>,
+    #[serde(default, skip_serializing_if = "CannonAim::is_fixed")]
+    pub aim: CannonAim,
+}
+
+impl Cannon {
+    /// Aim at the given time, player controlled cannons rest at their base rotation
+    pub fn rot_at(&self, time: f32) -> f32 {
+        match self.aim {
+            CannonAim::Oscillate { amplitude, period } => {
+                self.rot + amplitude * (2.0 * f32::PI * time / period.max(EPS)).sin()
+            }
+            CannonAim::Fixed | CannonAim::Player { .. } => self.rot,
+        }
+    }
 }
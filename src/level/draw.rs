@@ -16,13 +16,24 @@ struct SurfaceVertex {
     a_flow: f32,
 }
 
+type SurfaceMesh = HashMap<String, ugli::VertexBuffer<SurfaceVertex>>;
+type TileMesh = HashMap<String, ugli::VertexBuffer<TileVertex>>;
+
+/// Trigger group and `enabled` flag shared by the elements of a mesh
+type MeshGroup = (Option<String>, bool);
+
 pub struct LayerMesh {
-    tiles: HashMap<String, ugli::VertexBuffer<TileVertex>>,
-    surfaces: HashMap<String, ugli::VertexBuffer<SurfaceVertex>>,
+    /// Elements switched by triggers are split by group so they can be hidden without a rebuild
+    tiles: HashMap<MeshGroup, TileMesh>,
+    surfaces: HashMap<MeshGroup, SurfaceMesh>,
+    /// Breakable gameplay surfaces get a mesh each so they can be hidden without a rebuild
+    breakables: Vec<(usize, SurfaceMesh)>,
 }
 
 pub struct LevelMesh {
     layers: Vec<LayerMesh>,
+    /// Built once in the authored position and moved with a model matrix when drawn
+    platforms: Vec<SurfaceMesh>,
 }
 
 impl LevelMesh {
@@ -48,354 +59,379 @@ impl LevelMesh {
             let r = surface_texture_radius(a);
             (angle * r).abs()
         };
+        let surfaces_mesh = |surfaces: &[Surface]| -> SurfaceMesh {
+            let mut vertex_data: HashMap<String, Vec<SurfaceVertex>> = HashMap::new();
+
+            type Key = usize;
+            let mut vertex_ts: HashMap<Key, f32> = default();
+            let mut queue = std::collections::VecDeque::<Key>::new();
+            for key in 0..surfaces.len() {
+                if vertex_ts.contains_key(&key) {
+                    continue;
+                }
+                vertex_ts.insert(key, 0.0);
+                queue.push_back(key);
+                let mut this_pass = Vec::new();
+                while let Some(key) = queue.pop_front() {
+                    this_pass.push(key);
+                    let surface = &surfaces[key];
+                    let start_t = *vertex_ts.get(&key).unwrap();
+                    let end_t = start_t + (surface.p2 - surface.p1).len();
+                    for (i, other) in surfaces.iter().enumerate() {
+                        if other.type_name != surface.type_name {
+                            continue;
+                        }
+                        let mut push = |key: Key, t: f32| {
+                            vertex_ts.entry(key).or_insert_with(|| {
+                                queue.push_back(key);
+                                t
+                            });
+                        };
+                        for (t, p) in [(start_t, surface.p1), (end_t, surface.p2)] {
+                            if p == other.p1 {
+                                push(i, t + arc_len(surface, other));
+                            }
+                            if p == other.p2 {
+                                push(i, t - (other.p2 - other.p1).len() - arc_len(other, surface));
+                            }
+                        }
+                    }
+                }
+
+                let mut this_pass_vertex_data = HashMap::<String, Vec<SurfaceVertex>>::new();
+
+                for i in this_pass {
+                    let surface = &surfaces[i];
+                    let normal = (surface.p2 - surface.p1).normalize().rotate_90();
+                    let start_t = *vertex_ts.get(&i).unwrap();
+                    let len = (surface.p2 - surface.p1).len();
+                    let end_t = start_t + len;
+
+                    let prev = surfaces.iter().find(|other| {
+                        other.p2 == surface.p1 && other.type_name == surface.type_name
+                    });
+                    let next = surfaces.iter().find(|other| {
+                        other.p1 == surface.p2 && other.type_name == surface.type_name
+                    });
+
+                    // Rect
+                    this_pass_vertex_data
+                        .entry(surface.type_name.clone())
+                        .or_default()
+                        .extend({
+                            let dir = surface.p2 - surface.p1;
+                            let start_ratio = prev
+                                .map_or(0.0, |prev| {
+                                    ray_hit_time(
+                                        surface.p1
+                                            + surface.normal() * surface_texture_height(surface),
+                                        dir,
+                                        prev.p1 + prev.normal() * surface_texture_height(prev),
+                                        prev.normal(),
+                                    )
+                                })
+                                .max(0.0);
+                            let end_ratio = next
+                                .map_or(1.0, |next| {
+                                    ray_hit_time(
+                                        surface.p1
+                                            + surface.normal() * surface_texture_height(surface),
+                                        dir,
+                                        next.p1 + next.normal() * surface_texture_height(next),
+                                        next.normal(),
+                                    )
+                                })
+                                .min(1.0);
+                            let p1 = surface.p1 + dir * start_ratio;
+                            let p2 = surface.p1 + dir * end_ratio;
+                            let vs = [
+                                SurfaceVertex {
+                                    a_pos: p1,
+                                    a_normal: normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(start_t, 0.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p2,
+                                    a_normal: normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(end_t, 0.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p2 + surface_texture_height(surface) * normal,
+                                    a_normal: normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(end_t, 1.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p1 + surface_texture_height(surface) * normal,
+                                    a_normal: normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(start_t, 1.0),
+                                },
+                            ];
+                            [vs[0], vs[1], vs[2], vs[0], vs[2], vs[3]]
+                        });
+
+                    // Corner to the next segment
+                    if let Some(next) = next {
+                        const R: usize = 100;
+                        fn lerp(a: vec2<f32>, b: vec2<f32>, t: f32) -> vec2<f32> {
+                            a * (1.0 - t) + b * t
+                        }
+                        let n1 = normal;
+                        let n2 = (next.p2 - next.p1).rotate_90().normalize_or_zero();
+                        struct Point {
+                            pos: vec2<f32>,
+                            normal: vec2<f32>,
+                            height: f32,
+                        }
+                        let mut vs = Vec::<Point>::new();
+                        if vec2::skew(surface.normal(), next.normal()) > EPS {
+                            let ray_start =
+                                surface.p1 + surface.normal() * surface_texture_height(surface);
+                            let ray_dir = surface.p2 - surface.p1;
+                            let t = ray_hit_time(
+                                ray_start,
+                                ray_dir,
+                                next.p1 + next.normal() * surface_texture_height(next),
+                                next.normal(),
+                            );
+                            let mid = ray_start + ray_dir * t;
+                            let p1 = mid - surface.normal() * surface_texture_height(surface);
+                            let p2 = mid - next.normal() * surface_texture_height(next);
+                            for (p1, p2) in [(p1, surface.p2), (surface.p2, p2)] {
+                                for j in 0..=R {
+                                    let pos = lerp(p1, p2, j as f32 / R as f32);
+                                    if vs.last().map(|p| p.pos) == Some(pos) {
+                                        // LUL
+                                        continue;
+                                    }
+                                    vs.push(Point {
+                                        pos,
+                                        normal: (mid - pos).normalize(),
+                                        height: (mid - pos).len(),
+                                    });
+                                }
+                            }
+                        } else {
+                            let a = n1.arg();
+                            let b = n2.arg();
+                            let mut angle = b - a;
+                            if angle > 0.0 {
+                                angle -= 2.0 * f32::PI;
+                            }
+                            for j in 0..=R {
+                                vs.push(Point {
+                                    pos: surface.p2,
+                                    normal: vec2(1.0, 0.0).rotate(a + angle * j as f32 / R as f32),
+                                    height: surface_texture_height(surface),
+                                });
+                            }
+                        }
+                        let (start_t, end_t) = {
+                            let start_t = end_t;
+                            (start_t, start_t + arc_len(surface, next))
+                        };
+                        for (i, seg) in vs.windows(2).enumerate() {
+                            let p1 = &seg[0];
+                            let p2 = &seg[1];
+                            let (start_t, end_t) = {
+                                (
+                                    start_t + (end_t - start_t) * i as f32 / vs.len() as f32,
+                                    start_t + (end_t - start_t) * (i + 1) as f32 / vs.len() as f32,
+                                )
+                            };
+                            let vs = [
+                                SurfaceVertex {
+                                    a_pos: p1.pos,
+                                    a_normal: p1.normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(start_t, 0.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p2.pos,
+                                    a_normal: p2.normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(end_t, 0.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p2.pos + p2.height * p2.normal,
+                                    a_normal: p2.normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(end_t, 1.0),
+                                },
+                                SurfaceVertex {
+                                    a_pos: p1.pos + p1.height * p1.normal,
+                                    a_normal: p1.normal,
+                                    a_flow: surface.flow,
+                                    a_vt: vec2(start_t, 1.0),
+                                },
+                            ];
+                            this_pass_vertex_data
+                                .entry(surface.type_name.clone())
+                                .or_default()
+                                .extend([vs[0], vs[1], vs[2], vs[0], vs[2], vs[3]]);
+                        }
+                    } else {
+                        log::warn!("Not connected????");
+                    }
+                }
+
+                let max = this_pass_vertex_data
+                    .values()
+                    .flatten()
+                    .map(|v| r32(v.a_vt.x))
+                    .max()
+                    .unwrap();
+                let min = this_pass_vertex_data
+                    .values()
+                    .flatten()
+                    .map(|v| r32(v.a_vt.x))
+                    .min()
+                    .unwrap();
+                let total_len = (max - min).raw();
+                let rounded_len = total_len.round().max(1.0);
+                for (name, mut data) in this_pass_vertex_data {
+                    for v in &mut data {
+                        v.a_vt.x *= rounded_len / total_len;
+                    }
+                    vertex_data.entry(name).or_default().extend(data);
+                }
+            }
+            vertex_data
+                .into_iter()
+                .map(|(type_name, data)| {
+                    let data = {
+                        // TODO should be handled differently
+                        let surface_assets = &assets.surfaces[&type_name];
+                        let texture = surface_assets
+                            .textures
+                            .front
+                            .as_ref()
+                            .or(surface_assets.textures.back.as_ref())
+                            .unwrap();
+                        let height = texture.size().y as f32 / texture.size().x as f32;
+                        let mut data = data;
+                        for vertex in &mut data {
+                            vertex.a_pos -= vertex.a_normal
+                                * height
+                                * surface_assets.params.texture_underground;
+                        }
+                        data
+                    };
+                    (type_name, ugli::VertexBuffer::new_static(geng.ugli(), data))
+                })
+                .collect()
+        };
+        let tiles_mesh = |tiles: &[&Tile], texture_scale: f32| -> TileMesh {
+            let mut vertex_data: HashMap<String, Vec<TileVertex>> = HashMap::new();
+            for tile in tiles {
+                let fadeout_distance =
+                    assets.tiles[&tile.type_name].params.fadeout_distance * texture_scale;
+                let data: [vec2<f32>; 3] = std::array::from_fn(|i| {
+                    let mut vs = tile.vertices;
+                    vs.rotate_left(i);
+                    let p1 = vs[1];
+                    let v1 = vs[0] - vs[1];
+                    let p2 = vs[2];
+                    let n2 = (vs[2] - vs[0]).rotate_90().normalize();
+                    let p1 = p1 + v1.rotate_90().normalize() * fadeout_distance;
+                    let p2 = p2 + n2 * fadeout_distance;
+                    let t = ray_hit_time(p1, v1, p2, n2);
+                    p1 + v1 * t
+                });
+                vertex_data
+                    .entry(tile.type_name.clone())
+                    .or_default()
+                    .extend(data.into_iter().map(|v| TileVertex {
+                        a_pos: v,
+                        a_side_distances: {
+                            let distances: [f32; 3] = std::array::from_fn(|i| {
+                                let n = (tile.vertices[i] - tile.vertices[(i + 1) % 3])
+                                    .rotate_90()
+                                    .normalize();
+                                (vec2::dot(v, n) - vec2::dot(tile.vertices[i], n))
+                                    / fadeout_distance
+                            });
+                            let [x, y, z] = distances;
+                            vec3(x, y, z)
+                        },
+                        a_corner_distances: {
+                            let distances: [f32; 3] = std::array::from_fn(|i| {
+                                let mut vs = tile.vertices;
+                                vs.rotate_left(i);
+                                let n1 = (vs[0] - vs[1]).rotate_90().normalize();
+                                let n2 = (vs[2] - vs[0]).rotate_90().normalize();
+                                let n = (n1 + n2).normalize();
+                                (vec2::dot(v, n) - vec2::dot(tile.vertices[i], n))
+                                    / fadeout_distance
+                            });
+                            let [x, y, z] = distances;
+                            vec3(x, y, z)
+                        },
+                        a_flow: tile.flow,
+                    }));
+            }
+            vertex_data
+                .into_iter()
+                .map(|(type_name, data)| {
+                    (type_name, ugli::VertexBuffer::new_static(geng.ugli(), data))
+                })
+                .collect()
+        };
+        let is_breakable = |layer: &LevelLayer, surface: &Surface| -> bool {
+            layer.gameplay && assets.surfaces[&surface.type_name].params.breakable()
+        };
         Self {
             layers: level
                 .layers
                 .iter()
                 .map(|layer| LayerMesh {
                     tiles: {
-                        let mut vertex_data: HashMap<String, Vec<TileVertex>> = HashMap::new();
+                        let mut groups: HashMap<MeshGroup, Vec<&Tile>> = HashMap::new();
                         for tile in &layer.tiles {
-                            let fadeout_distance =
-                                assets.tiles[&tile.type_name].params.fadeout_distance
-                                    * layer.texture_scale;
-                            let data: [vec2<f32>; 3] = std::array::from_fn(|i| {
-                                let mut vs = tile.vertices;
-                                vs.rotate_left(i);
-                                let p1 = vs[1];
-                                let v1 = vs[0] - vs[1];
-                                let p2 = vs[2];
-                                let n2 = (vs[2] - vs[0]).rotate_90().normalize();
-                                let p1 = p1 + v1.rotate_90().normalize() * fadeout_distance;
-                                let p2 = p2 + n2 * fadeout_distance;
-                                let t = ray_hit_time(p1, v1, p2, n2);
-                                p1 + v1 * t
-                            });
-                            vertex_data
-                                .entry(tile.type_name.clone())
+                            groups
+                                .entry((tile.group.clone(), tile.enabled))
                                 .or_default()
-                                .extend(data.into_iter().map(|v| TileVertex {
-                                    a_pos: v,
-                                    a_side_distances: {
-                                        let distances: [f32; 3] = std::array::from_fn(|i| {
-                                            let n = (tile.vertices[i] - tile.vertices[(i + 1) % 3])
-                                                .rotate_90()
-                                                .normalize();
-                                            (vec2::dot(v, n) - vec2::dot(tile.vertices[i], n))
-                                                / fadeout_distance
-                                        });
-                                        let [x, y, z] = distances;
-                                        vec3(x, y, z)
-                                    },
-                                    a_corner_distances: {
-                                        let distances: [f32; 3] = std::array::from_fn(|i| {
-                                            let mut vs = tile.vertices;
-                                            vs.rotate_left(i);
-                                            let n1 = (vs[0] - vs[1]).rotate_90().normalize();
-                                            let n2 = (vs[2] - vs[0]).rotate_90().normalize();
-                                            let n = (n1 + n2).normalize();
-                                            (vec2::dot(v, n) - vec2::dot(tile.vertices[i], n))
-                                                / fadeout_distance
-                                        });
-                                        let [x, y, z] = distances;
-                                        vec3(x, y, z)
-                                    },
-                                    a_flow: tile.flow,
-                                }));
+                                .push(tile);
                         }
-                        vertex_data
+                        groups
                             .into_iter()
-                            .map(|(type_name, data)| {
-                                (type_name, ugli::VertexBuffer::new_static(geng.ugli(), data))
-                            })
+                            .map(|(group, tiles)| (group, tiles_mesh(&tiles, layer.texture_scale)))
                             .collect()
                     },
                     surfaces: {
-                        let mut vertex_data: HashMap<String, Vec<SurfaceVertex>> = HashMap::new();
-
-                        type Key = usize;
-                        let mut vertex_ts: HashMap<Key, f32> = default();
-                        let mut queue = std::collections::VecDeque::<Key>::new();
-                        for key in 0..layer.surfaces.len() {
-                            if vertex_ts.contains_key(&key) {
-                                continue;
-                            }
-                            vertex_ts.insert(key, 0.0);
-                            queue.push_back(key);
-                            let mut this_pass = Vec::new();
-                            while let Some(key) = queue.pop_front() {
-                                this_pass.push(key);
-                                let surface = &layer.surfaces[key];
-                                let start_t = *vertex_ts.get(&key).unwrap();
-                                let end_t = start_t + (surface.p2 - surface.p1).len();
-                                for (i, other) in layer.surfaces.iter().enumerate() {
-                                    if other.type_name != surface.type_name {
-                                        continue;
-                                    }
-                                    let mut push = |key: Key, t: f32| {
-                                        vertex_ts.entry(key).or_insert_with(|| {
-                                            queue.push_back(key);
-                                            t
-                                        });
-                                    };
-                                    for (t, p) in [(start_t, surface.p1), (end_t, surface.p2)] {
-                                        if p == other.p1 {
-                                            push(i, t + arc_len(surface, other));
-                                        }
-                                        if p == other.p2 {
-                                            push(
-                                                i,
-                                                t - (other.p2 - other.p1).len()
-                                                    - arc_len(other, surface),
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-
-                            let mut this_pass_vertex_data =
-                                HashMap::<String, Vec<SurfaceVertex>>::new();
-
-                            for i in this_pass {
-                                let surface = &layer.surfaces[i];
-                                let normal = (surface.p2 - surface.p1).normalize().rotate_90();
-                                let start_t = *vertex_ts.get(&i).unwrap();
-                                let len = (surface.p2 - surface.p1).len();
-                                let end_t = start_t + len;
-
-                                let prev = layer.surfaces.iter().find(|other| {
-                                    other.p2 == surface.p1 && other.type_name == surface.type_name
-                                });
-                                let next = layer.surfaces.iter().find(|other| {
-                                    other.p1 == surface.p2 && other.type_name == surface.type_name
-                                });
-
-                                // Rect
-                                this_pass_vertex_data
-                                    .entry(surface.type_name.clone())
+                        let mut groups: HashMap<MeshGroup, Vec<Surface>> = HashMap::new();
+                        for surface in &layer.surfaces {
+                            if !is_breakable(layer, surface) {
+                                groups
+                                    .entry((surface.group.clone(), surface.enabled))
                                     .or_default()
-                                    .extend({
-                                        let dir = surface.p2 - surface.p1;
-                                        let start_ratio = prev
-                                            .map_or(0.0, |prev| {
-                                                ray_hit_time(
-                                                    surface.p1
-                                                        + surface.normal()
-                                                            * surface_texture_height(surface),
-                                                    dir,
-                                                    prev.p1
-                                                        + prev.normal()
-                                                            * surface_texture_height(prev),
-                                                    prev.normal(),
-                                                )
-                                            })
-                                            .max(0.0);
-                                        let end_ratio = next
-                                            .map_or(1.0, |next| {
-                                                ray_hit_time(
-                                                    surface.p1
-                                                        + surface.normal()
-                                                            * surface_texture_height(surface),
-                                                    dir,
-                                                    next.p1
-                                                        + next.normal()
-                                                            * surface_texture_height(next),
-                                                    next.normal(),
-                                                )
-                                            })
-                                            .min(1.0);
-                                        let p1 = surface.p1 + dir * start_ratio;
-                                        let p2 = surface.p1 + dir * end_ratio;
-                                        let vs = [
-                                            SurfaceVertex {
-                                                a_pos: p1,
-                                                a_normal: normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(start_t, 0.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p2,
-                                                a_normal: normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(end_t, 0.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p2
-                                                    + surface_texture_height(surface) * normal,
-                                                a_normal: normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(end_t, 1.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p1
-                                                    + surface_texture_height(surface) * normal,
-                                                a_normal: normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(start_t, 1.0),
-                                            },
-                                        ];
-                                        [vs[0], vs[1], vs[2], vs[0], vs[2], vs[3]]
-                                    });
-
-                                // Corner to the next segment
-                                if let Some(next) = next {
-                                    const R: usize = 100;
-                                    fn lerp(a: vec2<f32>, b: vec2<f32>, t: f32) -> vec2<f32> {
-                                        a * (1.0 - t) + b * t
-                                    }
-                                    let n1 = normal;
-                                    let n2 = (next.p2 - next.p1).rotate_90().normalize_or_zero();
-                                    struct Point {
-                                        pos: vec2<f32>,
-                                        normal: vec2<f32>,
-                                        height: f32,
-                                    }
-                                    let mut vs = Vec::<Point>::new();
-                                    if vec2::skew(surface.normal(), next.normal()) > EPS {
-                                        let ray_start = surface.p1
-                                            + surface.normal() * surface_texture_height(surface);
-                                        let ray_dir = surface.p2 - surface.p1;
-                                        let t = ray_hit_time(
-                                            ray_start,
-                                            ray_dir,
-                                            next.p1 + next.normal() * surface_texture_height(next),
-                                            next.normal(),
-                                        );
-                                        let mid = ray_start + ray_dir * t;
-                                        let p1 = mid
-                                            - surface.normal() * surface_texture_height(surface);
-                                        let p2 = mid - next.normal() * surface_texture_height(next);
-                                        for (p1, p2) in [(p1, surface.p2), (surface.p2, p2)] {
-                                            for j in 0..=R {
-                                                let pos = lerp(p1, p2, j as f32 / R as f32);
-                                                if vs.last().map(|p| p.pos) == Some(pos) {
-                                                    // LUL
-                                                    continue;
-                                                }
-                                                vs.push(Point {
-                                                    pos,
-                                                    normal: (mid - pos).normalize(),
-                                                    height: (mid - pos).len(),
-                                                });
-                                            }
-                                        }
-                                    } else {
-                                        let a = n1.arg();
-                                        let b = n2.arg();
-                                        let mut angle = b - a;
-                                        if angle > 0.0 {
-                                            angle -= 2.0 * f32::PI;
-                                        }
-                                        for j in 0..=R {
-                                            vs.push(Point {
-                                                pos: surface.p2,
-                                                normal: vec2(1.0, 0.0)
-                                                    .rotate(a + angle * j as f32 / R as f32),
-                                                height: surface_texture_height(surface),
-                                            });
-                                        }
-                                    }
-                                    let (start_t, end_t) = {
-                                        let start_t = end_t;
-                                        (start_t, start_t + arc_len(surface, next))
-                                    };
-                                    for (i, seg) in vs.windows(2).enumerate() {
-                                        let p1 = &seg[0];
-                                        let p2 = &seg[1];
-                                        let (start_t, end_t) = {
-                                            (
-                                                start_t
-                                                    + (end_t - start_t) * i as f32
-                                                        / vs.len() as f32,
-                                                start_t
-                                                    + (end_t - start_t) * (i + 1) as f32
-                                                        / vs.len() as f32,
-                                            )
-                                        };
-                                        let vs = [
-                                            SurfaceVertex {
-                                                a_pos: p1.pos,
-                                                a_normal: p1.normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(start_t, 0.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p2.pos,
-                                                a_normal: p2.normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(end_t, 0.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p2.pos + p2.height * p2.normal,
-                                                a_normal: p2.normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(end_t, 1.0),
-                                            },
-                                            SurfaceVertex {
-                                                a_pos: p1.pos + p1.height * p1.normal,
-                                                a_normal: p1.normal,
-                                                a_flow: surface.flow,
-                                                a_vt: vec2(start_t, 1.0),
-                                            },
-                                        ];
-                                        this_pass_vertex_data
-                                            .entry(surface.type_name.clone())
-                                            .or_default()
-                                            .extend([vs[0], vs[1], vs[2], vs[0], vs[2], vs[3]]);
-                                    }
-                                } else {
-                                    log::warn!("Not connected????");
-                                }
-                            }
-
-                            let max = this_pass_vertex_data
-                                .values()
-                                .flatten()
-                                .map(|v| r32(v.a_vt.x))
-                                .max()
-                                .unwrap();
-                            let min = this_pass_vertex_data
-                                .values()
-                                .flatten()
-                                .map(|v| r32(v.a_vt.x))
-                                .min()
-                                .unwrap();
-                            let total_len = (max - min).raw();
-                            let rounded_len = total_len.round().max(1.0);
-                            for (name, mut data) in this_pass_vertex_data {
-                                for v in &mut data {
-                                    v.a_vt.x *= rounded_len / total_len;
-                                }
-                                vertex_data.entry(name).or_default().extend(data);
+                                    .push(surface.clone());
                             }
                         }
-                        vertex_data
+                        groups
                             .into_iter()
-                            .map(|(type_name, data)| {
-                                let data = {
-                                    // TODO should be handled differently
-                                    let surface_assets = &assets.surfaces[&type_name];
-                                    let texture = surface_assets
-                                        .textures
-                                        .front
-                                        .as_ref()
-                                        .or(surface_assets.textures.back.as_ref())
-                                        .unwrap();
-                                    let height = texture.size().y as f32 / texture.size().x as f32;
-                                    let mut data = data;
-                                    for vertex in &mut data {
-                                        vertex.a_pos -= vertex.a_normal
-                                            * height
-                                            * surface_assets.params.texture_underground;
-                                    }
-                                    data
-                                };
-                                (type_name, ugli::VertexBuffer::new_static(geng.ugli(), data))
-                            })
+                            .map(|(group, surfaces)| (group, surfaces_mesh(&surfaces)))
                             .collect()
                     },
+                    breakables: layer
+                        .surfaces
+                        .iter()
+                        .enumerate()
+                        .filter(|(_index, surface)| is_breakable(layer, surface))
+                        .map(|(index, surface)| {
+                            (index, surfaces_mesh(std::slice::from_ref(surface)))
+                        })
+                        .collect(),
                 })
                 .collect(),
+            platforms: level
+                .platforms
+                .iter()
+                .map(|platform| surfaces_mesh(&platform.surfaces))
+                .collect(),
         }
     }
 }
@@ -422,54 +458,101 @@ impl Game {
     ) {
         let assets = self.assets.get();
         let camera = geng::Camera2d {
-            center: self.camera.center * level.layers[layer_index].parallax,
+            center: self.camera.center * self.layer_parallax(&level.layers[layer_index]),
             ..self.camera
         };
         let mesh = self.get_mesh(level);
 
-        for (type_name, data) in &mesh.layers[layer_index].surfaces {
-            let surface_assets = &assets.surfaces[type_name];
-            let texture = match texture(surface_assets) {
-                Some(texture) => texture,
-                None => continue,
+        let layer_mesh = &mesh.layers[layer_index];
+        let mut meshes: Vec<(&SurfaceMesh, mat3<f32>)> = layer_mesh
+            .surfaces
+            .iter()
+            .filter(|((group, enabled), _mesh)| level.is_enabled(group.as_deref(), *enabled))
+            .map(|(_group, mesh)| (mesh, mat3::identity()))
+            .collect();
+        for (index, breakable) in &layer_mesh.breakables {
+            let surface = &level.layers[layer_index].surfaces[*index];
+            if !level.is_enabled(surface.group.as_deref(), surface.enabled) {
+                continue;
+            }
+            let id = SurfaceId {
+                layer: layer_index,
+                index: *index,
             };
-            let texture_shift =
-                surface_assets.params.texture_speed * self.simulation_time * texture_move_direction;
-            ugli::draw(
-                framebuffer,
-                &assets.shaders.surface,
-                ugli::DrawMode::Triangles,
-                data,
-                (
-                    ugli::uniforms! {
-                        u_texture: &**texture,
-                        u_height: texture.size().y as f32 / texture.size().x as f32,
-                        u_simulation_time: self.simulation_time,
-                        u_flex_frequency: surface_assets.params.flex_frequency,
-                        u_flex_amplitude: surface_assets.params.flex_amplitude,
-                        u_texture_shift: texture_shift,
-                        u_layer_color: level.layers[layer_index].color,
-                        u_reveal_radius: level.layers[layer_index].reveal_radius,
-                    },
-                    camera.uniforms(self.framebuffer_size),
+            let model_matrix = match level.surface_state(id) {
+                None => mat3::identity(),
+                // Shake while crumbling
+                Some(SurfaceState::Crumbling { .. }) => mat3::translate(
+                    vec2(
+                        (self.level_time * 53.0 + *index as f32).sin(),
+                        (self.level_time * 47.0 + *index as f32).cos(),
+                    ) * 0.05,
                 ),
-                ugli::DrawParameters {
-                    blend_mode: Some(ugli::BlendMode::straight_alpha()),
-                    ..default()
-                },
+                Some(SurfaceState::Broken { .. }) => continue,
+            };
+            meshes.push((breakable, model_matrix));
+        }
+        // Platforms are drawn together with the first gameplay layer
+        if level.layers.iter().position(|layer| layer.gameplay) == Some(layer_index) {
+            meshes.extend(
+                mesh.platforms
+                    .iter()
+                    .zip(&level.platforms)
+                    .map(|(mesh, platform)| (mesh, platform.motion.transform(self.level_time))),
             );
         }
+        for (mesh, model_matrix) in meshes {
+            for (type_name, data) in mesh {
+                let surface_assets = &assets.surfaces[type_name];
+                let texture = match texture(surface_assets) {
+                    Some(texture) => texture,
+                    None => continue,
+                };
+                let texture_shift = surface_assets.params.texture_speed
+                    * self.simulation_time
+                    * texture_move_direction;
+                ugli::draw(
+                    framebuffer,
+                    &assets.shaders.surface,
+                    ugli::DrawMode::Triangles,
+                    data,
+                    (
+                        ugli::uniforms! {
+                            u_texture: &**texture,
+                            u_height: texture.size().y as f32 / texture.size().x as f32,
+                            u_simulation_time: self.simulation_time,
+                            u_flex_frequency: surface_assets.params.flex_frequency,
+                            u_flex_amplitude: surface_assets.params.flex_amplitude,
+                            u_texture_shift: texture_shift,
+                            u_layer_color: level.layers[layer_index].color,
+                            u_reveal_radius: level.layers[layer_index].reveal_radius,
+                            u_model_matrix: model_matrix,
+                        },
+                        camera.uniforms(self.framebuffer_size),
+                    ),
+                    ugli::DrawParameters {
+                        blend_mode: Some(ugli::BlendMode::straight_alpha()),
+                        ..default()
+                    },
+                );
+            }
+        }
     }
 
     fn draw_tiles(&self, framebuffer: &mut ugli::Framebuffer, level: &Level, layer_index: usize) {
         let assets = self.assets.get();
         let camera = geng::Camera2d {
-            center: self.camera.center * level.layers[layer_index].parallax,
+            center: self.camera.center * self.layer_parallax(&level.layers[layer_index]),
             ..self.camera
         };
         let mesh = self.get_mesh(level);
 
-        for (type_name, data) in &mesh.layers[layer_index].tiles {
+        let tiles = mesh.layers[layer_index]
+            .tiles
+            .iter()
+            .filter(|((group, enabled), _mesh)| level.is_enabled(group.as_deref(), *enabled))
+            .flat_map(|(_group, mesh)| mesh);
+        for (type_name, data) in tiles {
             let tile_assets = &assets.tiles[type_name];
             let texture_scale = vec2(tile_assets.texture.size().map(|x| x as f32).aspect(), 1.0)
                 * tile_assets.params.texture_scale
@@ -518,16 +601,29 @@ impl Game {
 
     pub fn draw_cannons(&self, level: &Level, framebuffer: &mut ugli::Framebuffer) {
         let assets = self.assets.get();
-        for cannon in &level.cannons {
+        for (index, cannon) in level.cannons.iter().enumerate() {
             let mut scale = vec2(1.0, 1.0);
             if cannon.rot > f32::PI / 2.0 || cannon.rot < -f32::PI / 2.0 {
                 scale.x = -scale.x;
             }
+            // A loaded guy holds the current aim
+            let rot = self
+                .guys
+                .iter()
+                .find(|guy| {
+                    guy.state
+                        .cannon_timer
+                        .as_ref()
+                        .map_or(false, |timer| timer.cannon_index == index)
+                })
+                .map_or(cannon.rot_at(self.level_time), |guy| {
+                    guy.state.rot + f32::PI / 2.0
+                });
             self.geng.draw2d().draw2d(
                 framebuffer,
                 &self.camera,
                 &draw2d::TexturedQuad::unit(&assets.cannon.body)
-                    .rotate(cannon.rot)
+                    .rotate(rot)
                     .translate(cannon.pos),
             );
             self.geng.draw2d().draw2d(
@@ -554,6 +650,43 @@ impl Game {
         }
     }
 
+    pub fn draw_triggers(&self, level: &Level, framebuffer: &mut ugli::Framebuffer) {
+        let size = self.config.trigger.size;
+        for (index, trigger) in level.triggers.iter().enumerate() {
+            let active = level.is_trigger_active(index);
+            match trigger.kind {
+                TriggerKind::PressurePlate => {
+                    // Pressed plates sink into the floor
+                    let height = if active { 0.1 } else { 0.25 };
+                    let bottom = trigger.pos - vec2(size, size / 2.0);
+                    self.geng.draw2d().draw2d(
+                        framebuffer,
+                        &self.camera,
+                        &draw2d::Quad::new(
+                            Aabb2::from_corners(bottom, bottom + vec2(size * 2.0, height)),
+                            trigger.color,
+                        ),
+                    );
+                }
+                TriggerKind::Pickup => {
+                    if active {
+                        continue;
+                    }
+                    self.geng.draw2d().draw2d(
+                        framebuffer,
+                        &self.camera,
+                        &draw2d::Quad::new(
+                            Aabb2::point(vec2::ZERO).extend_uniform(size / 2.0),
+                            trigger.color,
+                        )
+                        .rotate(self.real_time)
+                        .translate(trigger.pos),
+                    );
+                }
+            }
+        }
+    }
+
     pub fn draw_layer_back(
         &self,
         level: &Level,
@@ -564,15 +697,17 @@ impl Game {
         self.draw_tiles(framebuffer, level, layer_index);
         {
             for obj in &level.layers[layer_index].objects {
+                let object_assets = &assets.objects[&obj.type_name];
+                // Pickups spin to stand out
+                let spin = matches!(
+                    object_assets.params.effect,
+                    Some(ObjectEffect::SetFartType(_))
+                );
                 self.geng.draw2d().draw2d(
                     framebuffer,
                     &self.camera,
-                    &draw2d::TexturedQuad::unit(&assets.objects[&obj.type_name])
-                        .transform(mat3::rotate(if obj.fart_type().is_some() {
-                            self.real_time
-                        } else {
-                            0.0
-                        }))
+                    &draw2d::TexturedQuad::unit(&object_assets.texture)
+                        .transform(mat3::rotate(if spin { self.real_time } else { 0.0 }))
                         .scale_uniform(0.6)
                         .translate(obj.pos),
                 );
@@ -586,6 +721,7 @@ impl Game {
             1.0,
         );
         self.draw_portals(level, framebuffer);
+        self.draw_triggers(level, framebuffer);
     }
 
     pub fn draw_layer_front(
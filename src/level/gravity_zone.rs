@@ -0,0 +1,54 @@
+use super::*;
+
+/// Polygon where gravity is replaced, zero gravity makes a floaty section
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct GravityZone {
+    pub vertices: Vec<vec2<f32>>,
+    pub gravity: vec2<f32>,
+    /// Rotate the camera so that this gravity points down on screen
+    #[serde(default)]
+    pub rotate_camera: bool,
+}
+
+impl GravityZone {
+    pub fn contains(&self, pos: vec2<f32>) -> bool {
+        // Even-odd rule so that the polygon does not need a specific winding
+        let mut inside = false;
+        for (i, &p1) in self.vertices.iter().enumerate() {
+            let p2 = self.vertices[(i + 1) % self.vertices.len()];
+            if (p1.y > pos.y) != (p2.y > pos.y)
+                && pos.x < p1.x + (p2.x - p1.x) * (pos.y - p1.y) / (p2.y - p1.y)
+            {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct LocalGravity {
+    pub gravity: vec2<f32>,
+    pub rotate_camera: bool,
+}
+
+impl Level {
+    /// Gravity override at the point, zones take priority over tiles
+    pub fn gravity_at(&self, pos: vec2<f32>, assets: &Assets) -> Option<LocalGravity> {
+        if let Some(zone) = self.gravity_zones.iter().find(|zone| zone.contains(pos)) {
+            return Some(LocalGravity {
+                gravity: zone.gravity,
+                rotate_camera: zone.rotate_camera,
+            });
+        }
+        self.gameplay_tiles_near(Aabb2::point(pos))
+            .filter(|tile| tile.contains(pos))
+            .find_map(|tile| {
+                let params = &assets.tiles[&tile.type_name].params;
+                params.gravity.map(|gravity| LocalGravity {
+                    gravity,
+                    rotate_camera: params.rotate_camera,
+                })
+            })
+    }
+}
@@ -0,0 +1,187 @@
+use super::*;
+
+/// Size of a grid cell, a few guy radiuses so most queries touch only a couple of cells
+const CELL_SIZE: f32 = 4.0;
+
+fn cell_of(pos: vec2<f32>) -> (i32, i32) {
+    (
+        (pos.x / CELL_SIZE).floor() as i32,
+        (pos.y / CELL_SIZE).floor() as i32,
+    )
+}
+
+pub struct GridItem {
+    pub layer: usize,
+    pub index: usize,
+    pub bounds: Aabb2<f32>,
+}
+
+/// Uniform grid of items, every item is stored in all the cells its bounds touch
+#[derive(Default)]
+pub struct Grid {
+    items: Vec<GridItem>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl Grid {
+    fn insert(&mut self, item: GridItem) {
+        let min = cell_of(item.bounds.min);
+        let max = cell_of(item.bounds.max);
+        for x in min.0..=max.0 {
+            for y in min.1..=max.1 {
+                self.cells.entry((x, y)).or_default().push(self.items.len());
+            }
+        }
+        self.items.push(item);
+    }
+
+    /// Items whose bounds touch the area, each reported once
+    pub fn query(&self, area: Aabb2<f32>) -> impl Iterator<Item = &GridItem> + '_ {
+        let min = cell_of(area.min);
+        let max = cell_of(area.max);
+        (min.0..=max.0)
+            .flat_map(move |x| (min.1..=max.1).map(move |y| (x, y)))
+            .filter_map(move |cell| Some((cell, self.cells.get(&cell)?)))
+            .flat_map(move |(cell, items)| items.iter().map(move |&index| (cell, index)))
+            .filter_map(move |(cell, index)| {
+                let item = &self.items[index];
+                let bounds = item.bounds;
+                if bounds.max.x < area.min.x
+                    || bounds.max.y < area.min.y
+                    || bounds.min.x > area.max.x
+                    || bounds.min.y > area.max.y
+                {
+                    return None;
+                }
+                // Only the cell with the corner of the overlap reports the item
+                let corner = vec2(bounds.min.x.max(area.min.x), bounds.min.y.max(area.min.y));
+                (cell_of(corner) == cell).then_some(item)
+            })
+    }
+}
+
+/// Acceleration structure for the static parts of a level
+pub struct SpatialGrid {
+    pub gameplay_surfaces: Grid,
+    pub gameplay_tiles: Grid,
+    /// Surface and tile vertices on all layers, used for snapping in the editor
+    pub points: Grid,
+    /// Expected path segments, index is the position in [SpatialGrid::progress_segments]
+    pub progress: Grid,
+    /// Start, end and path length before the segment
+    pub progress_segments: Vec<(vec2<f32>, vec2<f32>, f32)>,
+    pub progress_total_len: f32,
+}
+
+impl SpatialGrid {
+    pub fn new(level: &LevelInfo) -> Self {
+        let mut gameplay_surfaces = Grid::default();
+        let mut gameplay_tiles = Grid::default();
+        let mut points = Grid::default();
+        for (layer_index, layer) in level.layers.iter().enumerate() {
+            for (index, surface) in layer.surfaces.iter().enumerate() {
+                if layer.gameplay {
+                    gameplay_surfaces.insert(GridItem {
+                        layer: layer_index,
+                        index,
+                        bounds: Aabb2::points_bounding_box([surface.p1, surface.p2]),
+                    });
+                }
+                for p in [surface.p1, surface.p2] {
+                    points.insert(GridItem {
+                        layer: layer_index,
+                        index,
+                        bounds: Aabb2::point(p),
+                    });
+                }
+            }
+            for (index, tile) in layer.tiles.iter().enumerate() {
+                if layer.gameplay {
+                    gameplay_tiles.insert(GridItem {
+                        layer: layer_index,
+                        index,
+                        bounds: Aabb2::points_bounding_box(tile.vertices),
+                    });
+                }
+                for p in tile.vertices {
+                    points.insert(GridItem {
+                        layer: layer_index,
+                        index,
+                        bounds: Aabb2::point(p),
+                    });
+                }
+            }
+        }
+
+        let mut progress = Grid::default();
+        let mut progress_segments = Vec::new();
+        let mut progress_total_len = 0.0;
+        for path in &level.expected_path {
+            for window in path.windows(2) {
+                let (a, b) = (window[0], window[1]);
+                progress.insert(GridItem {
+                    layer: 0,
+                    index: progress_segments.len(),
+                    bounds: Aabb2::points_bounding_box([a, b]),
+                });
+                progress_segments.push((a, b, progress_total_len));
+                progress_total_len += (b - a).len();
+            }
+        }
+
+        Self {
+            gameplay_surfaces,
+            gameplay_tiles,
+            points,
+            progress,
+            progress_segments,
+            progress_total_len,
+        }
+    }
+}
+
+impl Level {
+    pub(super) fn grid(&self) -> &SpatialGrid {
+        self.grid.get_or_init(|| {
+            log::debug!("Creating level grid");
+            SpatialGrid::new(&self.info)
+        })
+    }
+
+    /// Gameplay surfaces that may touch the area, broken and disabled surfaces are skipped
+    pub fn gameplay_surface_ids_near(
+        &self,
+        area: Aabb2<f32>,
+    ) -> impl Iterator<Item = (SurfaceId, &Surface)> {
+        self.grid()
+            .gameplay_surfaces
+            .query(area)
+            .map(|item| SurfaceId {
+                layer: item.layer,
+                index: item.index,
+            })
+            .filter(|&id| !self.is_broken(id))
+            .map(|id| (id, &self.layers[id.layer].surfaces[id.index]))
+            .filter(|(_id, surface)| self.is_enabled(surface.group.as_deref(), surface.enabled))
+    }
+
+    /// Gameplay surfaces that may touch the area, broken and disabled surfaces are skipped
+    pub fn gameplay_surfaces_near(&self, area: Aabb2<f32>) -> impl Iterator<Item = &Surface> {
+        self.gameplay_surface_ids_near(area)
+            .map(|(_id, surface)| surface)
+    }
+
+    /// Gameplay tiles that may touch the area, disabled tiles are skipped
+    pub fn gameplay_tiles_near(&self, area: Aabb2<f32>) -> impl Iterator<Item = &Tile> {
+        self.grid()
+            .gameplay_tiles
+            .query(area)
+            .map(|item| &self.layers[item.layer].tiles[item.index])
+            .filter(|tile| self.is_enabled(tile.group.as_deref(), tile.enabled))
+    }
+
+    /// Surface and tile vertices on all layers inside the area
+    pub fn points_near(&self, area: Aabb2<f32>) -> impl Iterator<Item = vec2<f32>> + '_ {
+        self.grid().points.query(area).map(|item| item.bounds.min)
+    }
+}
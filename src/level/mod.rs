@@ -1,18 +1,31 @@
 use super::*;
 
+mod breakable;
+mod camera_zone;
 mod cannon;
 mod draw;
+mod gravity_zone;
+mod grid;
 mod object;
+mod platform;
 mod portal;
 mod progress;
 mod surface;
 mod tile;
+mod trigger;
+mod wind_zone;
 
+pub use breakable::*;
+pub use camera_zone::*;
 pub use cannon::*;
+pub use gravity_zone::*;
 pub use object::*;
+pub use platform::*;
 pub use portal::*;
 pub use surface::*;
 pub use tile::*;
+pub use trigger::*;
+pub use wind_zone::*;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct LevelLayer {
@@ -51,6 +64,16 @@ pub struct LevelInfo {
     pub layers: Vec<LevelLayer>,
     pub cannons: Vec<Cannon>,
     pub portals: Vec<Portal>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub camera_zones: Vec<CameraZone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platforms: Vec<Platform>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gravity_zones: Vec<GravityZone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<Trigger>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wind_zones: Vec<WindZone>,
     pub max_progress_distance: f32,
 }
 
@@ -73,6 +96,11 @@ impl Default for LevelInfo {
             }],
             cannons: vec![],
             portals: vec![],
+            camera_zones: vec![],
+            platforms: vec![],
+            gravity_zones: vec![],
+            triggers: vec![],
+            wind_zones: vec![],
             max_progress_distance: 10.0,
         }
     }
@@ -111,6 +139,18 @@ impl LevelInfo {
     pub fn all_objects(&self) -> impl Iterator<Item = &Object> {
         self.layers.iter().flat_map(|layer| &layer.objects)
     }
+
+    /// Stable hash of the level contents, used to detect that the level has changed
+    pub fn identity(&self) -> u64 {
+        // FNV-1a, since std hashers are not guaranteed to be stable between builds
+        let data = serde_json::to_vec(self).unwrap();
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in data {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        hash
+    }
 }
 
 #[derive(Deref)]
@@ -119,6 +159,11 @@ pub struct Level {
     #[deref]
     info: LevelInfo,
     mesh: RefCell<Option<draw::LevelMesh>>,
+    grid: std::cell::OnceCell<grid::SpatialGrid>,
+    surface_states: HashMap<SurfaceId, SurfaceState>,
+    trigger_states: HashMap<usize, TriggerState>,
+    /// Groups with an active trigger
+    active_groups: std::collections::HashSet<String>,
     history: Vec<LevelInfo>,
     history_index: usize,
     saved: bool,
@@ -144,6 +189,10 @@ impl Level {
             path: path.to_owned(),
             info,
             mesh: RefCell::new(None),
+            grid: default(),
+            surface_states: default(),
+            trigger_states: default(),
+            active_groups: default(),
             history: vec![],
             history_index: 0,
             saved,
@@ -152,8 +201,16 @@ impl Level {
     pub fn info(&self) -> &LevelInfo {
         &self.info
     }
+    /// Forgets broken surfaces and active triggers, for when the level itself changes
+    pub fn reset_runtime_state(&mut self) {
+        self.surface_states.clear();
+        self.trigger_states.clear();
+        self.active_groups.clear();
+    }
     pub fn modify(&mut self) -> &mut LevelInfo {
         *self.mesh.get_mut() = None;
+        self.grid = default();
+        self.reset_runtime_state();
         self.saved = false;
         self.history.truncate(self.history_index);
         self.history.push(self.info.clone());
@@ -163,6 +220,8 @@ impl Level {
     pub fn undo(&mut self) {
         if self.history_index > 0 {
             *self.mesh.get_mut() = None;
+            self.grid = default();
+            self.reset_runtime_state();
             self.saved = false;
             if self.history_index >= self.history.len() {
                 assert!(self.history_index == self.history.len());
@@ -175,6 +234,8 @@ impl Level {
     pub fn redo(&mut self) {
         if self.history_index + 1 < self.history.len() {
             *self.mesh.get_mut() = None;
+            self.grid = default();
+            self.reset_runtime_state();
             self.saved = false;
             self.history_index += 1;
             self.info = self.history[self.history_index].clone();
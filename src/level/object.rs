@@ -6,40 +6,78 @@ pub struct Object {
     pub pos: vec2<f32>,
 }
 
-impl Object {
-    // TODO: this should go into config I guess
-    pub fn fart_type(&self) -> Option<&str> {
-        Some(match self.type_name.as_str() {
-            "unicorn" => "rainbow",
-            "hot-pepper" => "fire",
-            "guitar" => "melody",
-            _ => return None,
-        })
-    }
+/// What happens when a guy touches an object
+#[derive(Deserialize, Clone, Debug)]
+pub enum ObjectEffect {
+    SetFartType(String),
+    Bubble,
+    /// Impulse applied when entering, divided by the guy's mass so heavier guys are pushed less
+    Impulse(vec2<f32>),
+    Checkpoint,
+    /// Moves the guy to the next object of the same type in the level
+    Teleport,
+}
+
+/// What picking up a timed effect does while the same effect is active
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EffectStacking {
+    /// Restart the timer
+    #[default]
+    Refresh,
+    /// Add the duration to the time left
+    Extend,
+    /// Keep the time left
+    Ignore,
+}
+
+#[derive(geng::asset::Load, Deserialize, Debug)]
+#[load(json)]
+pub struct ObjectParams {
+    #[serde(default = "default_trigger_radius")]
+    pub trigger_radius: f32,
+    /// Objects without an effect are just decorations
+    pub effect: Option<ObjectEffect>,
+    /// How long the effect lasts, fart types without a duration are kept until changed
+    pub duration: Option<f32>,
+    #[serde(default)]
+    pub stacking: EffectStacking,
+    /// Sound played when entering the object, relative to the assets directory
+    pub sound: Option<std::path::PathBuf>,
 }
 
-pub fn load_objects_assets(
-    manager: &geng::asset::Manager,
-    path: &std::path::Path,
-) -> geng::asset::Future<HashMap<String, Texture>> {
-    let manager = manager.clone();
-    let path = path.to_owned();
-    async move {
-        let json = <String as geng::asset::Load>::load(&manager, &path.join("_list.json")).await?;
-        let list: Vec<String> = serde_json::from_str(&json).unwrap();
-        future::join_all(list.into_iter().map(|name| {
-            let manager = manager.clone();
-            let path = path.clone();
-            async move {
-                Ok((
-                    name.clone(),
-                    geng::asset::Load::load(&manager, &path.join(format!("{}.png", name))).await?,
-                ))
-            }
-        }))
-        .await
-        .into_iter()
-        .collect::<Result<_, anyhow::Error>>()
+fn default_trigger_radius() -> f32 {
+    1.0
+}
+
+/// Params live in a json file next to the object svg
+pub struct ObjectAssets {
+    pub params: ObjectParams,
+    pub texture: Texture,
+    pub sound: Option<geng::Sound>,
+}
+
+impl geng::asset::Load for ObjectAssets {
+    fn load(manager: &geng::asset::Manager, path: &std::path::Path) -> geng::asset::Future<Self> {
+        let manager = manager.clone();
+        let path = path.to_owned();
+        async move {
+            let params: ObjectParams = manager.load(path.with_extension("json")).await?;
+            let texture = manager.load(path.with_extension("svg")).await?;
+            let sound = match &params.sound {
+                Some(sound) => {
+                    let assets_dir = path.parent().unwrap().parent().unwrap();
+                    Some(manager.load(assets_dir.join(sound)).await?)
+                }
+                None => None,
+            };
+            Ok(Self {
+                params,
+                texture,
+                sound,
+            })
+        }
+        .boxed_local()
     }
-    .boxed_local()
+
+    const DEFAULT_EXT: Option<&'static str> = None;
 }
@@ -0,0 +1,134 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlatformKeyframe {
+    /// Offset from the authored position of the surfaces
+    pub offset: vec2<f32>,
+    /// Time it takes to move from this keyframe to the next one
+    pub duration: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PlatformMotion {
+    /// Moves through the keyframes, looping back from the last one to the first
+    Path { keyframes: Vec<PlatformKeyframe> },
+    /// Spins around the pivot like a wheel, speed is in radians per second
+    Rotate { pivot: vec2<f32>, speed: f32 },
+    /// Swings back and forth around the pivot like an axe
+    Swing {
+        pivot: vec2<f32>,
+        amplitude: f32,
+        period: f32,
+    },
+}
+
+/// Group of surfaces moving together.
+/// Motion only depends on the level clock so every guy sees the platforms in the same place.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Platform {
+    pub surfaces: Vec<Surface>,
+    pub motion: PlatformMotion,
+}
+
+impl PlatformMotion {
+    /// Keyframe index and progress towards the next keyframe
+    fn path_position(keyframes: &[PlatformKeyframe], time: f32) -> Option<(usize, f32)> {
+        let total: f32 = keyframes.iter().map(|keyframe| keyframe.duration).sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut time = time.rem_euclid(total);
+        for (index, keyframe) in keyframes.iter().enumerate() {
+            if time < keyframe.duration {
+                return Some((index, time / keyframe.duration));
+            }
+            time -= keyframe.duration;
+        }
+        None
+    }
+
+    fn angle(&self, time: f32) -> f32 {
+        match *self {
+            Self::Path { .. } => 0.0,
+            Self::Rotate { speed, .. } => speed * time,
+            Self::Swing {
+                amplitude, period, ..
+            } => amplitude * (time * 2.0 * f32::PI / period.max(EPS)).sin(),
+        }
+    }
+
+    fn angular_velocity(&self, time: f32) -> f32 {
+        match *self {
+            Self::Path { .. } => 0.0,
+            Self::Rotate { speed, .. } => speed,
+            Self::Swing {
+                amplitude, period, ..
+            } => {
+                let frequency = 2.0 * f32::PI / period.max(EPS);
+                amplitude * frequency * (time * frequency).cos()
+            }
+        }
+    }
+
+    pub fn transform(&self, time: f32) -> mat3<f32> {
+        match self {
+            Self::Path { keyframes } => {
+                let Some((index, t)) = Self::path_position(keyframes, time) else {
+                    return mat3::identity();
+                };
+                let from = keyframes[index].offset;
+                let to = keyframes[(index + 1) % keyframes.len()].offset;
+                // Smoothstep so the platform does not jerk at keyframes
+                let t = t * t * (3.0 - 2.0 * t);
+                mat3::translate(from + (to - from) * t)
+            }
+            Self::Rotate { pivot, .. } | Self::Swing { pivot, .. } => {
+                mat3::translate(*pivot) * mat3::rotate(self.angle(time)) * mat3::translate(-*pivot)
+            }
+        }
+    }
+
+    /// Velocity of the platform at the given (already moved) point
+    pub fn velocity_at(&self, time: f32, pos: vec2<f32>) -> vec2<f32> {
+        match self {
+            Self::Path { keyframes } => {
+                let Some((index, t)) = Self::path_position(keyframes, time) else {
+                    return vec2::ZERO;
+                };
+                let from = keyframes[index].offset;
+                let to = keyframes[(index + 1) % keyframes.len()].offset;
+                (to - from) * 6.0 * t * (1.0 - t) / keyframes[index].duration
+            }
+            Self::Rotate { pivot, .. } | Self::Swing { pivot, .. } => {
+                (pos - *pivot).rotate_90() * self.angular_velocity(time)
+            }
+        }
+    }
+}
+
+impl Platform {
+    /// Surfaces moved to where they are at the given time
+    pub fn surfaces_at(&self, time: f32) -> impl Iterator<Item = Surface> + '_ {
+        let transform = self.motion.transform(time);
+        let apply = move |p: vec2<f32>| (transform * p.extend(1.0)).xy();
+        self.surfaces.iter().map(move |surface| Surface {
+            p1: apply(surface.p1),
+            p2: apply(surface.p2),
+            ..surface.clone()
+        })
+    }
+}
+
+impl LevelInfo {
+    /// Moved platform surfaces together with the index of their platform
+    pub fn platform_surfaces(&self, time: f32) -> impl Iterator<Item = (usize, Surface)> + '_ {
+        self.platforms
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, platform)| {
+                platform
+                    .surfaces_at(time)
+                    .map(move |surface| (index, surface))
+            })
+    }
+}
@@ -5,4 +5,18 @@ pub struct Portal {
     pub pos: vec2<f32>,
     pub dest: Option<usize>,
     pub color: Rgba<f32>,
+    /// Direction the portal faces, guys leave along it and enter against it.
+    /// Momentum is only turned when both linked portals have a facing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub angle: Option<f32>,
+}
+
+impl Portal {
+    /// Rotation applied to momentum when going from this portal to the destination
+    pub fn rotation_to(&self, dest: &Portal) -> f32 {
+        match (self.angle, dest.angle) {
+            (Some(from), Some(to)) => to - from + f32::PI,
+            _ => 0.0,
+        }
+    }
 }
@@ -7,6 +7,15 @@ pub struct Surface {
     #[serde(default)]
     pub flow: f32,
     pub type_name: String,
+    /// Trigger group that switches this surface on and off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Whether the surface is there while its group is not triggered
+    #[serde(
+        default = "default_enabled",
+        skip_serializing_if = "is_default_enabled"
+    )]
+    pub enabled: bool,
 }
 
 impl Surface {
@@ -26,6 +35,57 @@ impl Surface {
         let t = vec2::dot(self.p1 - point, n) / vec2::dot(n, n);
         n * t
     }
+
+    /// Earliest time in `0..=1` when a circle moving by `movement` touches the surface,
+    /// together with the contact normal pointing from the surface to the circle
+    pub fn sweep_circle(
+        &self,
+        pos: vec2<f32>,
+        movement: vec2<f32>,
+        radius: f32,
+    ) -> Option<(f32, vec2<f32>)> {
+        let mut hit: Option<(f32, vec2<f32>)> = None;
+        let dir = (self.p2 - self.p1).normalize_or_zero();
+        let len = (self.p2 - self.p1).len();
+        for normal in [dir.rotate_90(), -dir.rotate_90()] {
+            if vec2::dot(movement, normal) >= 0.0 {
+                continue;
+            }
+            let t = ray_hit_time(pos, movement, self.p1 + normal * radius, normal);
+            let along = vec2::dot(pos + movement * t - self.p1, dir);
+            if (0.0..=1.0).contains(&t) && (0.0..=len).contains(&along) {
+                hit = Some((t, normal));
+            }
+        }
+        for p in [self.p1, self.p2] {
+            // |pos + movement * t - p| = radius
+            let d = pos - p;
+            let a = vec2::dot(movement, movement);
+            let b = 2.0 * vec2::dot(d, movement);
+            let c = vec2::dot(d, d) - radius.sqr();
+            let discriminant = b.sqr() - 4.0 * a * c;
+            if a < EPS || discriminant < 0.0 {
+                continue;
+            }
+            let t = (-b - discriminant.sqrt()) / (2.0 * a);
+            if (0.0..=1.0).contains(&t) && hit.map_or(true, |(hit_t, _)| t < hit_t) {
+                hit = Some((t, (d + movement * t).normalize_or_zero()));
+            }
+        }
+        hit
+    }
+
+    /// Whether a circle touching the surface with this contact normal and relative velocity
+    /// is stopped, one-way surfaces and fast enough hits on fallthrough surfaces let it pass
+    pub fn blocks(
+        &self,
+        normal: vec2<f32>,
+        relative_vel: vec2<f32>,
+        fallthrough_speed: Option<f32>,
+    ) -> bool {
+        vec2::dot(normal, relative_vel) > -fallthrough_speed.unwrap_or(1e9)
+            && vec2::skew(self.p2 - self.p1, normal) > 0.0
+    }
 }
 
 #[derive(geng::asset::Load, Deserialize, Debug)]
@@ -55,6 +115,16 @@ pub struct SurfaceParams {
     #[serde(default)]
     pub max_stick_force: f32,
     pub fallthrough_speed: Option<f32>,
+    /// Touching sends the guy back to the last checkpoint
+    #[serde(default)]
+    pub respawn_on_touch: bool,
+    /// Surface breaks this long after it is first touched
+    pub crumble_time: Option<f32>,
+    /// Surface breaks right away when hit with at least this impulse
+    pub break_impulse: Option<f32>,
+    /// Time until a broken surface comes back
+    #[serde(default = "default_respawn_time")]
+    pub respawn_time: f32,
     #[serde(default = "default_snow_falloff")]
     pub snow_falloff: f32,
     #[serde(default)]
@@ -67,6 +137,10 @@ fn default_snow_falloff() -> f32 {
     1.0
 }
 
+fn default_respawn_time() -> f32 {
+    3.0
+}
+
 #[derive(geng::asset::Load)]
 #[load(sequential)]
 pub struct SurfaceAssets {
@@ -125,3 +199,65 @@ impl SurfaceParams {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surface(p1: vec2<f32>, p2: vec2<f32>) -> Surface {
+        Surface {
+            p1,
+            p2,
+            flow: 0.0,
+            type_name: "test".to_owned(),
+            group: None,
+            enabled: true,
+        }
+    }
+
+    /// A guy shot out of a cannon moves much further than its radius in one step
+    const RADIUS: f32 = 0.5;
+    const VEL: vec2<f32> = vec2(0.0, -300.0);
+    const DELTA_TIME: f32 = 1.0 / 60.0;
+
+    #[test]
+    fn fast_guy_stops_at_thin_surface() {
+        let surface = surface(vec2(-1.0, 0.0), vec2(1.0, 0.0));
+        let pos = vec2(0.0, 1.0);
+        let movement = VEL * DELTA_TIME;
+        let (t, normal) = surface
+            .sweep_circle(pos, movement, RADIUS)
+            .expect("the sweep must not tunnel through");
+        assert!((normal - vec2(0.0, 1.0)).len() < 1e-4);
+        assert!(((pos + movement * t).y - RADIUS).abs() < 1e-4);
+        assert!(surface.blocks(normal, VEL, None));
+    }
+
+    #[test]
+    fn fast_guy_passes_fallthrough_surface() {
+        let surface = surface(vec2(-1.0, 0.0), vec2(1.0, 0.0));
+        let (_t, normal) = surface
+            .sweep_circle(vec2(0.0, 1.0), VEL * DELTA_TIME, RADIUS)
+            .unwrap();
+        assert!(!surface.blocks(normal, VEL, Some(10.0)));
+        // Slow hits still land on it
+        assert!(surface.blocks(normal, vec2(0.0, -5.0), Some(10.0)));
+    }
+
+    #[test]
+    fn fast_guy_passes_one_way_surface_from_behind() {
+        let surface = surface(vec2(-1.0, 0.0), vec2(1.0, 0.0));
+        let (_t, normal) = surface
+            .sweep_circle(vec2(0.0, -1.0), -VEL * DELTA_TIME, RADIUS)
+            .unwrap();
+        assert!(!surface.blocks(normal, -VEL, None));
+    }
+
+    #[test]
+    fn sweep_misses_past_the_end() {
+        let surface = surface(vec2(-1.0, 0.0), vec2(1.0, 0.0));
+        assert!(surface
+            .sweep_circle(vec2(2.0, 1.0), VEL * DELTA_TIME, RADIUS)
+            .is_none());
+    }
+}
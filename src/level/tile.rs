@@ -6,6 +6,15 @@ pub struct Tile {
     #[serde(default = "zero_vec")]
     pub flow: vec2<f32>,
     pub type_name: String,
+    /// Trigger group that switches this tile on and off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Whether the tile is there while its group is not triggered
+    #[serde(
+        default = "default_enabled",
+        skip_serializing_if = "is_default_enabled"
+    )]
+    pub enabled: bool,
 }
 
 #[derive(geng::asset::Load, Deserialize)]
@@ -26,6 +35,13 @@ pub struct TileParams {
     #[serde(default = "zero_vec")]
     pub additional_force: vec2<f32>,
     pub time_scale: Option<f32>,
+    /// Replaces the global gravity while the guy is inside
+    pub gravity: Option<vec2<f32>>,
+    #[serde(default)]
+    pub rotate_camera: bool,
+    /// Touching sends the guy back to the last checkpoint
+    #[serde(default)]
+    pub respawn_on_touch: bool,
     #[serde(default = "default_draw_times")]
     pub draw_times: usize,
     #[serde(default)]
@@ -34,6 +50,16 @@ pub struct TileParams {
     pub texture_rotation: f32,
 }
 
+impl Tile {
+    pub fn contains(&self, pos: vec2<f32>) -> bool {
+        (0..3).all(|i| {
+            let p1 = self.vertices[i];
+            let p2 = self.vertices[(i + 1) % 3];
+            vec2::skew(p2 - p1, pos - p1) >= 0.0
+        })
+    }
+}
+
 fn default_draw_times() -> usize {
     1
 }
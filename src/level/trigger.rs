@@ -0,0 +1,91 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TriggerKind {
+    /// Active while a guy is on it
+    PressurePlate,
+    /// Taken by touching it, then gone while active
+    Pickup,
+}
+
+/// Switches a named group of surfaces and tiles while active
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Trigger {
+    pub pos: vec2<f32>,
+    pub kind: TriggerKind,
+    pub group: String,
+    /// Time the trigger stays active after it is released or picked up,
+    /// pickups without it stay active until the guy that took them restarts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration: Option<f32>,
+    pub color: Rgba<f32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TriggerState {
+    /// Level time when the trigger turns off, `None` for never
+    pub until: Option<f32>,
+    /// Guy that activated the trigger
+    pub by: Id,
+}
+
+pub fn default_enabled() -> bool {
+    true
+}
+
+pub fn is_default_enabled(enabled: &bool) -> bool {
+    *enabled
+}
+
+impl Level {
+    pub fn is_trigger_active(&self, index: usize) -> bool {
+        self.trigger_states.contains_key(&index)
+    }
+
+    /// Whether a surface or tile is there, triggering its group flips the `enabled` flag
+    pub fn is_enabled(&self, group: Option<&str>, enabled: bool) -> bool {
+        enabled != group.map_or(false, |group| self.active_groups.contains(group))
+    }
+
+    /// Activates the triggers touched during a physics step and turns off expired ones.
+    /// Times are on the level clock so timers keep running when a guy finishes or restarts
+    pub fn update_triggers(&mut self, time: f32, touched: &[(usize, Id)]) {
+        for &(index, by) in touched {
+            let trigger = &self.info.triggers[index];
+            match trigger.kind {
+                TriggerKind::PressurePlate => {
+                    self.trigger_states.insert(
+                        index,
+                        TriggerState {
+                            until: Some(time + trigger.duration.unwrap_or(0.0)),
+                            by,
+                        },
+                    );
+                }
+                TriggerKind::Pickup => {
+                    self.trigger_states.entry(index).or_insert(TriggerState {
+                        until: trigger.duration.map(|duration| time + duration),
+                        by,
+                    });
+                }
+            }
+        }
+        self.trigger_states
+            .retain(|_index, state| state.until.map_or(true, |until| time <= until));
+        self.update_active_groups();
+    }
+
+    /// Turns off the triggers a guy activated, for when it restarts its run
+    pub fn release_triggers(&mut self, by: Id) {
+        self.trigger_states.retain(|_index, state| state.by != by);
+        self.update_active_groups();
+    }
+
+    fn update_active_groups(&mut self) {
+        self.active_groups = self
+            .trigger_states
+            .keys()
+            .map(|&index| self.info.triggers[index].group.clone())
+            .collect();
+    }
+}
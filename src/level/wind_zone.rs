@@ -0,0 +1,119 @@
+use super::*;
+
+/// Noise space units per world unit, so gusts vary across a zone
+const WIND_NOISE_SCALE: f64 = 0.2;
+
+/// Area where the wind pushes guys and farticles
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WindZone {
+    pub rect: Aabb2<f32>,
+    /// Average wind velocity
+    pub wind: vec2<f32>,
+    /// How much the gusts turn and change strength, 0 is a steady wind
+    #[serde(default)]
+    pub turbulence: f32,
+    /// How fast the gusts change
+    #[serde(default = "default_wind_frequency")]
+    pub frequency: f32,
+}
+
+fn default_wind_frequency() -> f32 {
+    0.5
+}
+
+impl WindZone {
+    /// Only depends on the simulation time so that every client sees the same gusts
+    pub fn wind_at(&self, pos: vec2<f32>, time: f32, noise: &impl NoiseFn<f64, 3>) -> vec2<f32> {
+        let sample = |offset: f64| {
+            noise.get([
+                pos.x as f64 * WIND_NOISE_SCALE,
+                pos.y as f64 * WIND_NOISE_SCALE,
+                time as f64 * self.frequency as f64 + offset,
+            ]) as f32
+        };
+        let angle = sample(0.0) * self.turbulence * f32::PI;
+        let strength = (1.0 + sample(100.0) * self.turbulence).max(0.0);
+        self.wind.rotate(angle) * strength
+    }
+}
+
+/// Streak drawn to show where the wind blows
+pub struct WindStreak {
+    pub pos: vec2<f32>,
+    pub vel: vec2<f32>,
+    /// Goes from 1 to 0 over the streak lifetime
+    pub t: f32,
+}
+
+impl LevelInfo {
+    /// Sum of the winds of all zones containing the point
+    pub fn wind_at(
+        &self,
+        pos: vec2<f32>,
+        time: f32,
+        noise: &impl NoiseFn<f64, 3>,
+    ) -> Option<vec2<f32>> {
+        self.wind_zones
+            .iter()
+            .filter(|zone| zone.rect.contains(pos))
+            .map(|zone| zone.wind_at(pos, time, noise))
+            .reduce(|a, b| a + b)
+    }
+}
+
+impl Game {
+    pub fn update_wind_streaks(&mut self, delta_time: f32) {
+        let config = &self.config.wind;
+        for zone in &self.level.wind_zones {
+            // Fractional spawns are decided randomly so that small zones still get streaks
+            let expected =
+                zone.rect.width() * zone.rect.height() * config.streak_density * delta_time
+                    / config.streak_lifetime;
+            let count = expected.floor() as usize
+                + usize::from(thread_rng().gen_bool(expected.fract() as f64));
+            for _ in 0..count {
+                let pos = vec2(
+                    thread_rng().gen_range(zone.rect.min.x..=zone.rect.max.x),
+                    thread_rng().gen_range(zone.rect.min.y..=zone.rect.max.y),
+                );
+                self.wind_streaks.push(WindStreak {
+                    pos,
+                    vel: zone.wind_at(pos, self.simulation_time, &self.noise),
+                    t: 1.0,
+                });
+            }
+        }
+        for streak in &mut self.wind_streaks {
+            streak.t -= delta_time / config.streak_lifetime;
+            match self
+                .level
+                .wind_at(streak.pos, self.simulation_time, &self.noise)
+            {
+                Some(wind) => streak.vel = wind,
+                None => streak.t = 0.0,
+            }
+            streak.pos += streak.vel * delta_time;
+        }
+        self.wind_streaks.retain(|streak| streak.t > 0.0);
+    }
+
+    pub fn draw_wind_streaks(&self, framebuffer: &mut ugli::Framebuffer) {
+        let config = &self.config.wind;
+        for streak in &self.wind_streaks {
+            // Fade in and out
+            let alpha = (streak.t * f32::PI).sin();
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                &self.camera,
+                &draw2d::Segment::new(
+                    Segment(streak.pos - streak.vel * config.streak_length, streak.pos),
+                    config.streak_width,
+                    Rgba {
+                        a: config.streak_color.a * alpha,
+                        ..config.streak_color
+                    },
+                ),
+            );
+        }
+    }
+}
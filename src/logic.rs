@@ -2,69 +2,36 @@ use super::*;
 
 impl Game {
     pub fn update_my_guy_input(&mut self) {
+        let accessibility = self.accessibility_radius();
         let my_guy = match self.my_guy.map(|id| self.guys.get_mut(&id).unwrap()) {
             Some(guy) => guy,
             None => return,
         };
-        my_guy.paused = self.show_customizer;
-        if self.show_customizer {
+        my_guy.paused = self.show_customizer || self.pause_menu.is_some();
+        if my_guy.paused {
             return;
         }
+        // Keyboard, mouse & gamepad
+        let value = |action| {
+            self.bindings.value(
+                &self.geng,
+                self.active_gamepad,
+                self.settings.gamepad_deadzone,
+                action,
+            )
+        };
         let mut new_input = Input {
-            roll_left: 0.0,
-            roll_right: 0.0,
-            force_fart: false,
+            roll_left: value(Action::RollLeft),
+            roll_right: value(Action::RollRight),
+            force_fart: value(Action::Fart) > 0.0,
         };
 
-        // Keyboard
-        if CONTROLS_LEFT
-            .iter()
-            .any(|&key| self.geng.window().is_key_pressed(key))
-        {
-            new_input.roll_left = 1.0;
-        }
-        if CONTROLS_RIGHT
-            .iter()
-            .any(|&key| self.geng.window().is_key_pressed(key))
-        {
-            new_input.roll_right = 1.0;
-        }
-        if CONTROLS_FORCE_FART
-            .iter()
-            .any(|&key| self.geng.window().is_key_pressed(key))
-            || self
-                .geng
-                .window()
-                .is_button_pressed(geng::MouseButton::Left)
-        {
-            new_input.force_fart = true;
-        }
-
-        // Gamepad
-        if let Some(gamepad) = self.active_gamepad {
-            let gilrs = self.geng.gilrs();
-            let gamepad = gilrs.gamepad(gamepad);
-            for axis in [gilrs::Axis::LeftStickX, gilrs::Axis::RightStickX] {
-                if let Some(axis) = gamepad.axis_data(axis) {
-                    let value = axis.value();
-                    if value < 0.0 {
-                        new_input.roll_left += -value;
-                    } else {
-                        new_input.roll_right += value;
-                    }
-                }
-            }
-            for button in [gilrs::Button::South] {
-                if let Some(button) = gamepad.button_data(button) {
-                    if button.is_pressed() {
-                        new_input.force_fart = true;
-                    }
-                }
-            }
-        }
+        // Touch
+        self.touch_controls
+            .apply_input(&mut new_input, self.framebuffer_size);
 
         // Accessibility
-        if let Some(radius) = self.opt.accessibility {
+        if let Some(radius) = accessibility {
             let p = (self.geng.window().cursor_position().map(|x| x as f32)
                 - self.framebuffer_size / 2.0)
                 / radius;
@@ -81,6 +48,12 @@ impl Game {
         new_input.roll_left = new_input.roll_left.clamp(0.0, 1.0);
         new_input.roll_right = new_input.roll_right.clamp(0.0, 1.0);
 
+        // Assists, once every input source is merged
+        self.assists.apply_input(&self.settings, &mut new_input);
+        if self.settings.assisted() {
+            my_guy.progress.assisted = true;
+        }
+
         if my_guy.input != new_input {
             my_guy.input = new_input;
             if let Some(con) = &mut self.connection {
@@ -95,7 +68,8 @@ impl Game {
     pub fn update_guys(&mut self, delta_time: f32) {
         let assets = self.assets.get();
         let is_colliding = |guy: &Guy, surface_type: &str| -> bool {
-            for surface in self.level.gameplay_surfaces() {
+            let area = Aabb2::point(guy.state.pos).extend_uniform(guy.radius());
+            for surface in self.level.gameplay_surfaces_near(area) {
                 let v = surface.vector_from(guy.state.pos);
                 let penetration = guy.radius() - v.len();
                 if penetration > EPS && surface.type_name == surface_type {
@@ -104,12 +78,17 @@ impl Game {
             }
             false
         };
+        let platform_surfaces: Vec<(usize, Surface)> =
+            self.level.platform_surfaces(self.level_time).collect();
+        let mut surface_hits = Vec::new();
+        let mut touched_triggers = Vec::new();
         for guy in &mut self.guys {
             if guy.paused {
                 continue;
             }
+            let guy_area = Aabb2::point(guy.state.pos).extend_uniform(self.config.guy_radius);
             let mut time_scale = 1.0;
-            for tile in self.level.gameplay_tiles() {
+            for tile in self.level.gameplay_tiles_near(guy_area) {
                 if !Aabb2::points_bounding_box(tile.vertices)
                     .extend_uniform(self.config.guy_radius)
                     .contains(guy.state.pos)
@@ -139,23 +118,6 @@ impl Game {
             if (guy.state.pos - self.level.finish_point).len() < 1.5 {
                 guy.progress.finished = true;
             }
-            {
-                let mut new_fart_type = None;
-                for object in self.level.gameplay_objects() {
-                    if (guy.state.pos - object.pos).len() < 1.5 {
-                        if let Some(fart_type) = object.fart_type() {
-                            new_fart_type = Some(fart_type.to_owned());
-                        }
-                    }
-                }
-                if let Some(new_fart_type) = new_fart_type {
-                    if new_fart_type != guy.state.fart_type {
-                        guy.state.fart_type = new_fart_type;
-                        guy.state.fart_pressure = self.config.max_fart_pressure;
-                    }
-                }
-            }
-
             // Bubble
             if let Some(time) = &mut guy.state.bubble_timer {
                 *time -= delta_time;
@@ -167,9 +129,111 @@ impl Game {
                     - guy.state.vel)
                     .clamp_len(..=self.config.bubble_acceleration * delta_time);
             }
+
+            // Timed fart type
+            if let Some(timer) = &mut guy.state.fart_type_timer {
+                timer.time_left -= delta_time;
+                if timer.time_left < 0.0 {
+                    guy.state.fart_type = mem::take(&mut timer.previous);
+                    guy.state.fart_type_timer = None;
+                }
+            }
+
+            // Objects
             for object in self.level.gameplay_objects() {
-                if (guy.state.pos - object.pos).len() < 1.0 && object.type_name == "bubbler" {
-                    guy.state.bubble_timer = Some(self.config.bubble_time);
+                let object_assets = &assets.objects[&object.type_name];
+                let params = &object_assets.params;
+                let Some(effect) = &params.effect else { continue };
+                let is_inside =
+                    |pos: vec2<f32>| -> bool { (pos - object.pos).len() < params.trigger_radius };
+                if !is_inside(guy.state.pos) {
+                    continue;
+                }
+                let entered = !is_inside(prev_state.pos);
+                if entered {
+                    if let Some(sound) = &object_assets.sound {
+                        let mut sfx = sound.effect();
+                        sfx.set_volume(
+                            (self.settings.sfx_volume()
+                                * (1.0
+                                    - (guy.state.pos - self.camera.center).len() / self.camera.fov))
+                                .clamp(0.0, 1.0) as f64,
+                        );
+                        sfx.set_speed(sfx_speed);
+                        sfx.play();
+                    }
+                }
+                match effect {
+                    ObjectEffect::SetFartType(fart_type) => match params.duration {
+                        None => {
+                            guy.state.fart_type_timer = None;
+                            if *fart_type != guy.state.fart_type {
+                                guy.state.fart_type = fart_type.clone();
+                                guy.state.fart_pressure = self.config.max_fart_pressure;
+                            }
+                        }
+                        Some(duration) => {
+                            if !entered {
+                                continue;
+                            }
+                            if *fart_type == guy.state.fart_type {
+                                if let Some(timer) = &mut guy.state.fart_type_timer {
+                                    match params.stacking {
+                                        EffectStacking::Refresh => {
+                                            timer.time_left = duration;
+                                            timer.duration = duration;
+                                        }
+                                        EffectStacking::Extend => {
+                                            timer.time_left += duration;
+                                            timer.duration = timer.duration.max(timer.time_left);
+                                        }
+                                        EffectStacking::Ignore => {}
+                                    }
+                                }
+                            } else {
+                                // Chained power-ups still go back to the original fart type
+                                let previous = match guy.state.fart_type_timer.take() {
+                                    Some(timer) => timer.previous,
+                                    None => guy.state.fart_type.clone(),
+                                };
+                                guy.state.fart_type_timer = Some(FartTypeTimer {
+                                    time_left: duration,
+                                    duration,
+                                    previous,
+                                });
+                                guy.state.fart_type = fart_type.clone();
+                                guy.state.fart_pressure = self.config.max_fart_pressure;
+                            }
+                        }
+                    },
+                    ObjectEffect::Bubble => {
+                        guy.state.bubble_timer =
+                            Some(params.duration.unwrap_or(self.config.bubble_time));
+                    }
+                    ObjectEffect::Impulse(impulse) => {
+                        if entered {
+                            guy.state.vel += *impulse / guy.mass(&self.config);
+                        }
+                    }
+                    ObjectEffect::Checkpoint => {
+                        guy.progress.checkpoint = Some(object.pos);
+                    }
+                    ObjectEffect::Teleport => {
+                        if entered {
+                            let same_type: Vec<&Object> = self
+                                .level
+                                .gameplay_objects()
+                                .filter(|other| other.type_name == object.type_name)
+                                .collect();
+                            let index = same_type
+                                .iter()
+                                .position(|other| std::ptr::eq(*other, object))
+                                .unwrap();
+                            guy.state.pos = same_type[(index + 1) % same_type.len()].pos;
+                            // Otherwise the destination would count as entered too
+                            break;
+                        }
+                    }
                 }
             }
 
@@ -179,9 +243,10 @@ impl Game {
                     if (guy.state.pos - cannon.pos).len() < self.config.cannon.activate_distance {
                         guy.state.long_farting = false;
                         guy.state.fart_pressure = 0.0;
+                        guy.state.rot = cannon.rot - f32::PI / 2.0;
                         guy.state.cannon_timer = Some(CannonTimer {
                             cannon_index: index,
-                            time: self.config.cannon.shoot_time,
+                            time: cannon.shoot_time.unwrap_or(self.config.cannon.shoot_time),
                         });
                     }
                 }
@@ -189,18 +254,27 @@ impl Game {
             if let Some(timer) = &mut guy.state.cannon_timer {
                 let cannon = &self.level.cannons[timer.cannon_index];
                 guy.state.pos = cannon.pos;
-                guy.state.rot = cannon.rot - f32::PI / 2.0;
+                // The guy is turned along with the cannon so its rotation holds the aim
+                let rot = match cannon.aim {
+                    CannonAim::Player { range, speed } => {
+                        let input = guy.input.roll_left - guy.input.roll_right;
+                        (guy.state.rot + f32::PI / 2.0 + input * speed * delta_time)
+                            .clamp(cannon.rot - range, cannon.rot + range)
+                    }
+                    _ => cannon.rot_at(self.level_time),
+                };
+                guy.state.rot = rot - f32::PI / 2.0;
                 timer.time -= delta_time;
                 if timer.time < 0.0 {
                     guy.state.cannon_timer = None;
-                    let dir = vec2(1.0, 0.0).rotate(cannon.rot);
+                    let dir = vec2(1.0, 0.0).rotate(rot);
                     guy.state.pos += dir * self.config.cannon.activate_distance * 1.01;
-                    guy.state.vel = dir * self.config.cannon.strength;
+                    guy.state.vel = dir * cannon.strength.unwrap_or(self.config.cannon.strength);
                     guy.state.w = 0.0;
 
                     let mut effect = assets.cannon.shot.effect();
                     effect.set_volume(
-                        (self.volume
+                        (self.settings.sfx_volume()
                             * 0.6
                             * (1.0 - (guy.state.pos - self.camera.center).len() / self.camera.fov))
                             .clamp(0.0, 1.0) as f64,
@@ -232,7 +306,7 @@ impl Game {
                         });
                     }
                 }
-                return;
+                continue;
             }
 
             if guy.progress.finished {
@@ -256,12 +330,31 @@ impl Game {
                 );
 
             if guy.state.bubble_timer.is_none() {
-                guy.state.vel.y -= self.config.gravity * delta_time;
+                let gravity = match self.level.gravity_at(guy.state.pos, &assets) {
+                    Some(local) => local.gravity,
+                    None => vec2(0.0, -self.config.gravity),
+                };
+                let gravity_multiplier =
+                    assets.farts[&guy.state.fart_type].config.gravity_multiplier;
+                guy.state.vel += gravity * gravity_multiplier * delta_time;
+            }
+
+            // Wind only pushes the guy up to its speed, never slows it down
+            if let Some(wind) = self
+                .level
+                .wind_at(guy.state.pos, self.simulation_time, &self.noise)
+            {
+                let direction = wind.normalize_or_zero();
+                let relative_vel_along_wind = vec2::dot(direction, wind - guy.state.vel).max(0.0);
+                guy.state.vel +=
+                    direction * relative_vel_along_wind * self.config.wind.guy_drag * delta_time
+                        / guy.mass(&self.config);
             }
 
             let mut in_water = false;
             let butt = guy.state.pos + vec2(0.0, -guy.state.radius * 0.9).rotate(guy.state.rot);
-            for tile in self.level.gameplay_tiles() {
+            let guy_area = Aabb2::point(guy.state.pos).extend_uniform(self.config.guy_radius);
+            for tile in self.level.gameplay_tiles_near(guy_area) {
                 if !Aabb2::points_bounding_box(tile.vertices)
                     .extend_uniform(self.config.guy_radius)
                     .contains(guy.state.pos)
@@ -288,15 +381,8 @@ impl Game {
                     * percentage;
                 // TODO inertia?
             }
-            'tile_loop: for tile in self.level.gameplay_tiles() {
-                for i in 0..3 {
-                    let p1 = tile.vertices[i];
-                    let p2 = tile.vertices[(i + 1) % 3];
-                    if vec2::skew(p2 - p1, butt - p1) < 0.0 {
-                        continue 'tile_loop;
-                    }
-                }
-                if tile.type_name == "water" {
+            for tile in self.level.gameplay_tiles_near(Aabb2::point(butt)) {
+                if tile.contains(butt) && tile.type_name == "water" {
                     in_water = true;
                 }
             }
@@ -307,22 +393,22 @@ impl Game {
                 guy.state.fart_type.as_str()
             };
             let fart_assets = &assets.farts[fart_type];
+            let fart_physics = fart_assets.config.physics(&self.config);
 
             let could_fart = guy.state.fart_pressure >= self.config.fart_pressure_released;
-            if self.config.fart_continued_force == 0.0 {
+            if fart_physics.continued_force == 0.0 {
                 guy.state.long_farting = false;
             }
             if guy.input.force_fart {
                 if guy.state.long_farting {
                     guy.state.fart_pressure -=
-                        delta_time * self.config.fart_continuation_pressure_speed;
+                        delta_time * fart_physics.continuation_pressure_speed;
                     if guy.state.fart_pressure < 0.0 {
                         guy.state.fart_pressure = 0.0;
                         guy.state.long_farting = false;
                     }
                 } else {
-                    guy.state.fart_pressure +=
-                        delta_time * self.config.force_fart_pressure_multiplier;
+                    guy.state.fart_pressure += delta_time * fart_physics.force_pressure_multiplier;
                 }
             } else {
                 guy.state.long_farting = false;
@@ -340,7 +426,7 @@ impl Game {
                         self.long_fart_sfx.remove(&guy.id);
                     } else {
                         sfx.sfx.set_volume(
-                            (self.volume
+                            (self.settings.sfx_volume()
                                 * (1.0
                                     - (guy.state.pos - self.camera.center).len() / self.camera.fov))
                                 .clamp(0.0, 1.0) as f64
@@ -349,7 +435,7 @@ impl Game {
                     }
                 }
             } else if let Some(sfx) = self.long_fart_sfx.get_mut(&guy.id) {
-                let volume = (self.volume
+                let volume = (self.settings.sfx_volume()
                     * (1.0 - (guy.state.pos - self.camera.center).len() / self.camera.fov))
                     .clamp(0.0, 1.0) as f64;
                 if fart_type != sfx.type_name {
@@ -409,7 +495,7 @@ impl Game {
                             t: 1.0,
                         });
                 }
-                guy.state.vel += vec2(0.0, self.config.fart_continued_force * delta_time)
+                guy.state.vel += vec2(0.0, fart_physics.continued_force * delta_time)
                     .rotate(guy.state.rot)
                     / guy.mass(&self.config);
             } else if (guy.state.fart_pressure >= self.config.fart_pressure_released
@@ -459,11 +545,11 @@ impl Game {
                         t: 1.0,
                     });
                 }
-                guy.state.vel += vec2(0.0, self.config.fart_strength).rotate(guy.state.rot)
-                    / guy.mass(&self.config);
+                guy.state.vel +=
+                    vec2(0.0, fart_physics.strength).rotate(guy.state.rot) / guy.mass(&self.config);
                 let mut effect = fart_assets.sfx.choose(&mut thread_rng()).unwrap().effect();
                 effect.set_volume(
-                    (self.volume
+                    (self.settings.sfx_volume()
                         * (1.0 - (guy.state.pos - self.camera.center).len() / self.camera.fov))
                         .clamp(0.0, 1.0) as f64,
                 );
@@ -473,7 +559,7 @@ impl Game {
                 // Growling stomach recharge
                 if Some(guy.id) == self.my_guy {
                     let mut effect = assets.sfx.fart_recharge.effect();
-                    effect.set_volume(self.volume as f64 * 0.5);
+                    effect.set_volume(self.settings.sfx_volume() as f64 * 0.5);
                     effect.play();
                 }
                 guy.animation.growl_progress = Some(0.0);
@@ -492,19 +578,86 @@ impl Game {
                 .stick_force
                 .clamp_len(..=self.config.stick_force_fadeout_speed * delta_time);
 
-            guy.state.pos += guy.state.vel * delta_time;
+            // Sweep the movement so that fast guys can not tunnel through surfaces
+            let movement = guy.state.vel * delta_time;
+            let swept_area = Aabb2::points_bounding_box([guy.state.pos, guy.state.pos + movement])
+                .extend_uniform(guy.radius());
+            let mut first_hit: Option<(f32, vec2<f32>)> = None;
+            let surfaces = self
+                .level
+                .gameplay_surfaces_near(swept_area)
+                .map(|surface| (surface, None))
+                .chain(
+                    platform_surfaces
+                        .iter()
+                        .map(|(index, surface)| (surface, Some(*index))),
+                );
+            for (surface, platform) in surfaces {
+                let params = &assets.surfaces[&surface.type_name].params;
+                if params.non_collidable {
+                    continue;
+                }
+                let surface_vel = |pos| {
+                    platform.map_or(vec2::ZERO, |index| {
+                        self.level.platforms[index]
+                            .motion
+                            .velocity_at(self.level_time, pos)
+                    })
+                };
+                // Sweep relative to the nearest point, then judge the hit at the contact point
+                let nearest = guy.state.pos + surface.vector_from(guy.state.pos);
+                let Some((t, normal)) = surface.sweep_circle(
+                    guy.state.pos,
+                    movement - surface_vel(nearest) * delta_time,
+                    guy.radius(),
+                ) else {
+                    continue;
+                };
+                let contact = guy.state.pos + movement * t - normal * guy.radius();
+                if surface.blocks(
+                    normal,
+                    guy.state.vel - surface_vel(contact),
+                    params.fallthrough_speed,
+                ) && first_hit.map_or(true, |(first_t, _)| t < first_t)
+                {
+                    first_hit = Some((t, normal));
+                }
+            }
+            // Slow hits are left to the penetration check below, only stop the guy
+            // when the step would go deeper than it can resolve
+            match first_hit.filter(|&(t, normal)| {
+                vec2::dot(movement * (1.0 - t), -normal) >= self.config.max_penetration
+            }) {
+                Some((t, normal)) => {
+                    guy.state.pos += movement * t - normal * self.config.max_penetration / 2.0;
+                }
+                None => guy.state.pos += movement,
+            }
             guy.state.rot += guy.state.w * delta_time;
 
             struct Collision<'a> {
                 penetration: f32,
                 normal: vec2<f32>,
                 surface: &'a Surface,
+                /// Static surfaces can be breakable, platforms can not
+                surface_id: Option<SurfaceId>,
                 assets: &'a SurfaceAssets,
+                /// Velocity of the moving platform the surface belongs to
+                surface_vel: vec2<f32>,
             }
 
             let mut collision_to_resolve = None;
             let mut was_colliding_water = was_colliding_water;
-            for surface in self.level.gameplay_surfaces() {
+            let surfaces = self
+                .level
+                .gameplay_surface_ids_near(Aabb2::point(guy.state.pos).extend_uniform(guy.radius()))
+                .map(|(id, surface)| (surface, Some(id), None))
+                .chain(
+                    platform_surfaces
+                        .iter()
+                        .map(|(index, surface)| (surface, None, Some(*index))),
+                );
+            for (surface, surface_id, platform) in surfaces {
                 let from_surface = -surface.vector_from(guy.state.pos);
                 let penetration = guy.radius() - from_surface.len();
                 if penetration > 0.0 {
@@ -515,7 +668,7 @@ impl Game {
                         if vec2::dot(from_surface, guy.state.vel).abs() > 0.5 {
                             let mut effect = assets.sfx.water_splash.effect();
                             effect.set_volume(
-                                (self.volume
+                                (self.settings.sfx_volume()
                                     * 0.6
                                     * (1.0
                                         - (guy.state.pos - self.camera.center).len()
@@ -560,17 +713,27 @@ impl Game {
                         continue;
                     }
                     let normal = from_surface.normalize_or_zero();
-                    let normal_vel = vec2::dot(normal, guy.state.vel);
+                    let surface_vel = platform.map_or(vec2::ZERO, |index| {
+                        self.level.platforms[index]
+                            .motion
+                            .velocity_at(self.level_time, guy.state.pos - from_surface)
+                    });
+                    let normal_vel = vec2::dot(normal, guy.state.vel - surface_vel);
                     if normal_vel < -EPS
-                        && normal_vel > -surface_assets.params.fallthrough_speed.unwrap_or(1e9)
-                        && vec2::skew(surface.p2 - surface.p1, normal) > 0.0
+                        && surface.blocks(
+                            normal,
+                            guy.state.vel - surface_vel,
+                            surface_assets.params.fallthrough_speed,
+                        )
                         && penetration < self.config.max_penetration
                     {
                         let collision = Collision {
                             penetration,
                             surface,
+                            surface_id,
                             normal,
                             assets: surface_assets,
+                            surface_vel,
                         };
                         collision_to_resolve = std::cmp::max_by_key(
                             collision_to_resolve,
@@ -596,6 +759,9 @@ impl Game {
 
                 let before = guy.state.clone();
 
+                // Resolve in the frame of the platform so it carries the guy along
+                guy.state.vel -= collision.surface_vel;
+
                 let normal_vel = vec2::dot(guy.state.vel, collision.normal);
                 let tangent = collision.normal.rotate_90();
                 let tangent_vel = vec2::dot(guy.state.vel, tangent) - guy.state.w * guy.radius()
@@ -611,11 +777,20 @@ impl Game {
                 guy.state.vel += tangent * friction_impulse / guy.mass(&self.config);
                 guy.state.w -= friction_impulse / guy.radius() / guy.mass(&self.config);
 
+                // Replay ghosts must not break surfaces under the live players
+                if let Some(id) = collision.surface_id {
+                    if collision.assets.params.breakable() && !guy.id.is_replay() {
+                        surface_hits.push(SurfaceHit { id, impulse });
+                    }
+                }
+
                 guy.state.vel -=
                     guy.state.vel * (delta_time * collision.assets.params.speed_friction).min(1.0);
                 guy.state.w -=
                     guy.state.w * (delta_time * collision.assets.params.rotation_friction).min(1.0);
 
+                guy.state.vel += collision.surface_vel;
+
                 // Stickiness
                 guy.state.stick_force = std::cmp::max_by_key(
                     guy.state.stick_force,
@@ -666,7 +841,7 @@ impl Game {
                     if volume > 0.0 {
                         let mut effect = sound.effect();
                         effect.set_volume(
-                            (self.volume
+                            (self.settings.sfx_volume()
                                 * volume
                                 * (1.0
                                     - (guy.state.pos - self.camera.center).len() / self.camera.fov))
@@ -680,18 +855,113 @@ impl Game {
                 guy.state.stick_force = vec2::ZERO;
             }
 
+            // Triggers, replay ghosts must not press them for the live players
+            if !guy.id.is_replay() {
+                for (index, trigger) in self.level.triggers.iter().enumerate() {
+                    let is_inside = |pos: vec2<f32>| -> bool {
+                        (pos - trigger.pos).len() < self.config.trigger.size
+                    };
+                    let touched = match trigger.kind {
+                        TriggerKind::PressurePlate => is_inside(guy.state.pos),
+                        TriggerKind::Pickup => {
+                            !is_inside(prev_state.pos) && is_inside(guy.state.pos)
+                        }
+                    };
+                    if touched {
+                        touched_triggers.push((index, guy.id));
+                    }
+                }
+            }
+
             // Portals
+            if let Some(time) = &mut guy.state.portal_cooldown {
+                *time -= delta_time;
+                if *time < 0.0 {
+                    guy.state.portal_cooldown = None;
+                }
+            }
             for portal in &self.level.portals {
+                if guy.state.portal_cooldown.is_some() {
+                    break;
+                }
                 let is_colliding =
                     |pos: vec2<f32>| -> bool { (pos - portal.pos).len() < self.config.portal.size };
                 if !is_colliding(prev_state.pos) && is_colliding(guy.state.pos) {
                     if let Some(dest) = portal.dest {
-                        guy.state.pos = self.level.portals[dest].pos;
+                        let dest = &self.level.portals[dest];
+                        let rotation = portal.rotation_to(dest);
+                        guy.state.pos = dest.pos;
+                        guy.state.vel = guy.state.vel.rotate(rotation);
+                        guy.state.stick_force = guy.state.stick_force.rotate(rotation);
+                        guy.state.rot += rotation;
+                        guy.state.portal_cooldown = Some(self.config.portal.cooldown);
                         break;
                     }
                 }
             }
+
+            // Hazards
+            let guy_area = Aabb2::point(guy.state.pos).extend_uniform(guy.radius());
+            let touching_surface = self
+                .level
+                .gameplay_surfaces_near(guy_area)
+                .chain(platform_surfaces.iter().map(|(_index, surface)| surface))
+                .any(|surface| {
+                    assets.surfaces[&surface.type_name].params.respawn_on_touch
+                        && surface.vector_from(guy.state.pos).len() < guy.radius()
+                });
+            let touching_tile = self.level.gameplay_tiles_near(guy_area).any(|tile| {
+                assets.tiles[&tile.type_name].params.respawn_on_touch
+                    && circle_triangle_intersect_percentage(
+                        guy.state.pos,
+                        guy.radius(),
+                        tile.vertices,
+                    ) > 0.0
+            });
+            if (touching_surface || touching_tile) && !guy.progress.finished {
+                let mut effect = assets.sfx.hazard.effect();
+                effect.set_volume(
+                    (self.settings.sfx_volume()
+                        * (1.0 - (guy.state.pos - self.camera.center).len() / self.camera.fov))
+                        .clamp(0.0, 1.0) as f64,
+                );
+                effect.set_speed(sfx_speed);
+                effect.play();
+
+                let fart_type = "normal";
+                let fart_assets = &assets.farts[fart_type];
+                let farticles = self.farticles.entry(fart_type.to_owned()).or_default();
+                for _ in 0..self.config.hazard.particle_count {
+                    farticles.push(Farticle {
+                        size: self.config.hazard.particle_size,
+                        pos: guy.state.pos,
+                        vel: vec2(
+                            thread_rng().gen_range(0.0..=self.config.hazard.particle_speed),
+                            0.0,
+                        )
+                        .rotate(thread_rng().gen_range(0.0..=2.0 * f32::PI)),
+                        rot: thread_rng().gen_range(0.0..2.0 * f32::PI),
+                        w: thread_rng().gen_range(
+                            -fart_assets.config.farticle_w..=fart_assets.config.farticle_w,
+                        ),
+                        colors: self.config.hazard.particle_colors.clone(),
+                        t: 1.0,
+                    });
+                }
+
+                // Same as respawning at the checkpoint, but for any guy
+                let pos = guy.progress.checkpoint.unwrap_or(self.level.spawn_point);
+                guy.state = Guy::new(guy.id, pos, false, &self.config).state;
+                if self.my_guy == Some(guy.id) {
+                    if let Some(con) = &mut self.connection {
+                        con.send(ClientMessage::Update(self.simulation_time, guy.clone()));
+                    }
+                }
+            }
         }
+        self.level
+            .update_surface_states(self.level_time, &surface_hits, &assets);
+        self.level.update_triggers(self.level_time, &touched_triggers);
     }
 
     pub fn handle_connection(&mut self) {
@@ -747,8 +1017,30 @@ impl Game {
         }
         self.guys.insert(new_guy);
         self.simulation_time = 0.0;
+        self.level.release_triggers(self.client_id);
         if let Some(con) = &mut self.connection {
             con.send(ClientMessage::Despawn);
         }
     }
+
+    /// Respawn at the last reached checkpoint (or spawn) without restarting the run,
+    /// only in practice mode so ranked runs can not skip a section
+    pub fn respawn_at_checkpoint(&mut self) {
+        if !self.practice_mode() {
+            return;
+        }
+        let Some(guy) = self.my_guy.and_then(|id| self.guys.get_mut(&id)) else { return };
+        if guy.progress.finished {
+            return;
+        }
+        let pos = guy.progress.checkpoint.unwrap_or(self.level.spawn_point);
+        let mut new_guy = Guy::new(guy.id, pos, false, &self.config);
+        new_guy.customization = guy.customization.clone();
+        new_guy.progress = guy.progress.clone();
+        new_guy.paused = guy.paused;
+        *guy = new_guy;
+        if let Some(con) = &mut self.connection {
+            con.send(ClientMessage::Update(self.simulation_time, guy.clone()));
+        }
+    }
 }
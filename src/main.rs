@@ -9,6 +9,9 @@ use geng::prelude::*;
 mod ui;
 
 mod assets;
+mod assist;
+mod bindings;
+mod camera;
 mod customizer;
 mod editor;
 mod farticle;
@@ -19,13 +22,22 @@ mod leaderboard;
 mod level;
 mod logic;
 mod net;
+mod pause_menu;
+mod practice;
 mod remote;
 mod replay;
+mod save;
+mod settings;
+mod split_screen;
 mod svg;
+mod touch;
 mod util;
 mod video_editor;
 
 pub use assets::*;
+pub use assist::*;
+pub use bindings::*;
+pub use camera::*;
 pub use customizer::*;
 pub use editor::*;
 pub use farticle::*;
@@ -36,8 +48,14 @@ pub use leaderboard::*;
 pub use level::*;
 pub use logic::*;
 pub use net::*;
+pub use pause_menu::*;
+pub use practice::*;
 pub use remote::*;
 pub use replay::*;
+pub use save::*;
+pub use settings::*;
+pub use split_screen::*;
+pub use touch::*;
 pub use util::*;
 
 #[derive(clap::Parser, Clone)]
@@ -56,6 +74,8 @@ pub struct Opt {
     pub video: Option<std::path::PathBuf>,
     #[clap(long)]
     pub accessibility: Option<f32>,
+    #[clap(long)]
+    pub practice: bool,
     #[clap(flatten)]
     pub geng: geng::CliArgs,
 }
@@ -48,7 +48,9 @@ impl net::Receiver<ClientMessage> for Client {
         match message {
             ClientMessage::ForceReset => state.messages.push(ServerMessage::ForceReset),
             ClientMessage::Ping => client.sender.send(ServerMessage::Pong),
-            ClientMessage::Update(t, guy) => {
+            ClientMessage::Update(t, mut guy) => {
+                guy.customization.name =
+                    CustomizationOptions::sanitize_name(&guy.customization.name);
                 match self.history.as_mut() {
                     None => {
                         self.history = Some(History::new(t, &guy));
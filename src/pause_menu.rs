@@ -0,0 +1,277 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PauseScreen {
+    Main,
+    Settings,
+    Controls,
+}
+
+#[derive(Clone)]
+pub enum PauseMenuMessage {
+    Resume,
+    Restart,
+    Customize,
+    Settings,
+    LeaveRoom,
+    RemoveLocalPlayers,
+    Setting(SettingsItem),
+    Controls,
+    Rebind(Action),
+    ResetBindings,
+    Back,
+}
+
+impl Game {
+    pub fn open_pause_menu(&mut self) {
+        self.pause_menu = Some(PauseScreen::Main);
+        self.ui_controller.reset_selection();
+    }
+
+    fn pause_menu_buttons(&self) -> Vec<ui::Button<PauseMenuMessage>> {
+        let Some(screen) = self.pause_menu else { return vec![] };
+        let options: Vec<(String, PauseMenuMessage)> = match screen {
+            PauseScreen::Main => {
+                let mut options = vec![
+                    ("resume".to_owned(), PauseMenuMessage::Resume),
+                    ("restart".to_owned(), PauseMenuMessage::Restart),
+                    ("customize".to_owned(), PauseMenuMessage::Customize),
+                    ("settings".to_owned(), PauseMenuMessage::Settings),
+                ];
+                if self.connection.is_some() {
+                    options.push(("leave room".to_owned(), PauseMenuMessage::LeaveRoom));
+                }
+                if !self.local_players.is_empty() {
+                    options.push((
+                        "remove local players".to_owned(),
+                        PauseMenuMessage::RemoveLocalPlayers,
+                    ));
+                }
+                options
+            }
+            PauseScreen::Settings => SettingsItem::ALL
+                .into_iter()
+                .map(|item| (self.setting_label(item), PauseMenuMessage::Setting(item)))
+                .chain([
+                    ("controls".to_owned(), PauseMenuMessage::Controls),
+                    ("back".to_owned(), PauseMenuMessage::Back),
+                ])
+                .collect(),
+            PauseScreen::Controls => self
+                .bindings
+                .actions()
+                .map(|action| {
+                    let text = if self.rebinding == Some(action) {
+                        format!("{}: press any button...", action.name())
+                    } else {
+                        self.bindings.label(action)
+                    };
+                    (text, PauseMenuMessage::Rebind(action))
+                })
+                .chain([
+                    (
+                        "reset to defaults".to_owned(),
+                        PauseMenuMessage::ResetBindings,
+                    ),
+                    ("back".to_owned(), PauseMenuMessage::Back),
+                ])
+                .collect(),
+        };
+        let spacing = (7.0 / options.len() as f32).min(1.0);
+        options
+            .into_iter()
+            .enumerate()
+            .map(|(index, (text, message))| {
+                ui::Button::new(
+                    &text,
+                    vec2(0.0, 2.5 - index as f32 * spacing),
+                    spacing * 0.7,
+                    0.5,
+                    message,
+                )
+            })
+            .collect()
+    }
+
+    pub fn draw_pause_menu(&mut self, framebuffer: &mut ugli::Framebuffer) {
+        let Some(screen) = self.pause_menu else { return };
+        let camera = geng::Camera2d {
+            center: vec2::ZERO,
+            rotation: 0.0,
+            fov: 10.0,
+        };
+        self.geng.draw2d().draw2d(
+            framebuffer,
+            &camera,
+            &draw2d::Quad::new(
+                Aabb2::point(vec2::ZERO).extend_uniform(100.0),
+                Rgba::new(1.0, 1.0, 1.0, 0.7),
+            ),
+        );
+        let title = match screen {
+            PauseScreen::Main => "paused",
+            PauseScreen::Settings => "settings",
+            PauseScreen::Controls => "controls",
+        };
+        self.assets.get().font.draw(
+            framebuffer,
+            &camera,
+            title,
+            vec2::splat(geng::TextAlign::CENTER),
+            mat3::translate(vec2(0.0, 3.5)),
+            Rgba::new(0.5, 0.5, 1.0, 1.0),
+        );
+        if screen == PauseScreen::Controls {
+            self.assets.get().font.draw(
+                framebuffer,
+                &camera,
+                "click an action to add a binding, backspace to clear, delete to cancel",
+                vec2::splat(geng::TextAlign::CENTER),
+                mat3::translate(vec2(0.0, -4.5)) * mat3::scale_uniform(0.4),
+                Rgba::new(0.5, 0.5, 0.5, 1.0),
+            );
+        }
+        let buttons = self.pause_menu_buttons();
+        self.ui_controller.draw(framebuffer, &camera, buttons);
+    }
+
+    pub fn handle_pause_menu_event(&mut self, event: &geng::Event) {
+        if let Some(action) = self.rebinding {
+            let binding = match event {
+                geng::Event::KeyDown {
+                    key: geng::Key::Backspace,
+                } => {
+                    self.bindings.clear(action);
+                    self.rebinding = None;
+                    return;
+                }
+                // Not escape, so that it can be bound too
+                geng::Event::KeyDown {
+                    key: geng::Key::Delete,
+                } => {
+                    self.rebinding = None;
+                    return;
+                }
+                _ => Binding::from_event(&self.geng, event),
+            };
+            if let Some(binding) = binding {
+                self.bindings.bind(action, binding);
+                self.rebinding = None;
+            }
+            return;
+        }
+        if self.pause_menu == Some(PauseScreen::Settings) {
+            let delta = match event {
+                geng::Event::KeyDown {
+                    key: geng::Key::Left,
+                }
+                | geng::Event::Gamepad(gilrs::Event {
+                    event: gilrs::EventType::ButtonPressed(gilrs::Button::DPadLeft, ..),
+                    ..
+                }) => Some(-1),
+                geng::Event::KeyDown {
+                    key: geng::Key::Right,
+                }
+                | geng::Event::Gamepad(gilrs::Event {
+                    event: gilrs::EventType::ButtonPressed(gilrs::Button::DPadRight, ..),
+                    ..
+                }) => Some(1),
+                _ => None,
+            };
+            let item = self
+                .ui_controller
+                .selected()
+                .and_then(|index| SettingsItem::ALL.get(index));
+            if let (Some(delta), Some(&item)) = (delta, item) {
+                self.change_setting(item, delta, false);
+                return;
+            }
+        }
+        let buttons = self.pause_menu_buttons();
+        let msgs = self.ui_controller.handle_event(event, buttons).into_iter();
+        let back = matches!(
+            event,
+            geng::Event::KeyDown {
+                key: geng::Key::Escape,
+            } | geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::ButtonPressed(gilrs::Button::East, ..),
+                ..
+            })
+        ) || self
+            .bindings
+            .triggered(&self.geng, event)
+            .contains(&Action::Pause);
+        let mut msgs = msgs.chain(back.then_some(PauseMenuMessage::Back));
+        // Buttons are rebuilt after any change, so only the first message is handled
+        let Some(msg) = msgs.next() else { return };
+        match msg {
+            PauseMenuMessage::Resume => {
+                self.pause_menu = None;
+            }
+            PauseMenuMessage::Restart => {
+                self.pause_menu = None;
+                self.respawn_my_guy();
+            }
+            PauseMenuMessage::Customize => {
+                self.pause_menu = None;
+                self.show_customizer = true;
+                self.ui_controller.reset_selection();
+            }
+            PauseMenuMessage::Settings => {
+                self.pause_menu = Some(PauseScreen::Settings);
+                self.ui_controller.reset_selection();
+            }
+            PauseMenuMessage::LeaveRoom => {
+                self.pause_menu = None;
+                self.leave_room();
+            }
+            PauseMenuMessage::RemoveLocalPlayers => {
+                self.remove_local_players();
+                self.ui_controller.reset_selection();
+            }
+            PauseMenuMessage::Setting(item) => {
+                self.change_setting(item, 1, true);
+            }
+            PauseMenuMessage::Controls => {
+                self.pause_menu = Some(PauseScreen::Controls);
+                self.ui_controller.reset_selection();
+            }
+            PauseMenuMessage::Rebind(action) => {
+                self.rebinding = Some(action);
+            }
+            PauseMenuMessage::ResetBindings => {
+                self.bindings = Bindings::new(self.assets.get().emotes.len());
+                self.bindings.save();
+            }
+            PauseMenuMessage::Back => {
+                self.pause_menu = match self.pause_menu {
+                    Some(PauseScreen::Settings) => Some(PauseScreen::Main),
+                    Some(PauseScreen::Controls) => Some(PauseScreen::Settings),
+                    _ => None,
+                };
+                self.ui_controller.reset_selection();
+            }
+        }
+    }
+
+    fn change_setting(&mut self, item: SettingsItem, delta: i32, wrap: bool) {
+        self.settings.adjust(item, delta, wrap);
+        match item {
+            SettingsItem::CameraFov => self.camera.fov = self.settings.camera_fov,
+            SettingsItem::GameSpeed => self.time_scale = self.settings.game_speed,
+            _ => {}
+        }
+    }
+
+    /// Disconnect from the server and continue playing alone
+    pub fn leave_room(&mut self) {
+        if let Some(mut con) = self.connection.take() {
+            con.send(ClientMessage::Despawn);
+        }
+        let local_players: Vec<Id> = self.local_players.iter().map(|player| player.id).collect();
+        self.guys
+            .retain(|guy| Some(guy.id) == self.my_guy || local_players.contains(&guy.id));
+        self.remote_updates.clear();
+        self.emotes.clear();
+    }
+}
@@ -0,0 +1,62 @@
+use super::*;
+
+pub const SAVESTATE_SLOTS: usize = 4;
+
+#[derive(Clone)]
+pub struct Savestate {
+    pub guy: Guy,
+    pub simulation_time: f32,
+}
+
+impl Game {
+    pub fn practice_mode(&self) -> bool {
+        self.opt.practice || self.opt.editor
+    }
+
+    pub fn save_state(&mut self) {
+        let Some(guy) = self.my_guy.and_then(|id| self.guys.get(&id)) else { return };
+        self.savestates[self.selected_savestate] = Some(Savestate {
+            guy: guy.clone(),
+            simulation_time: self.simulation_time,
+        });
+    }
+
+    pub fn load_state(&mut self) {
+        let Some(save) = self.savestates[self.selected_savestate].clone() else { return };
+        self.respawn_my_guy();
+        let mut guy = save.guy;
+        guy.progress.used_savestates = true;
+        *self.guys.get_mut(&self.my_guy.unwrap()).unwrap() = guy;
+        self.simulation_time = save.simulation_time;
+    }
+
+    pub fn select_next_savestate(&mut self) {
+        self.selected_savestate = (self.selected_savestate + 1) % self.savestates.len();
+    }
+
+    pub fn draw_practice(&self, framebuffer: &mut ugli::Framebuffer) {
+        if !self.practice_mode() || self.show_customizer || self.editor.is_some() {
+            return;
+        }
+        let camera = geng::Camera2d {
+            center: vec2::ZERO,
+            rotation: 0.0,
+            fov: 10.0,
+        };
+        let slot = self.selected_savestate + 1;
+        let total = self.savestates.len();
+        let state = if self.savestates[self.selected_savestate].is_some() {
+            "saved"
+        } else {
+            "empty"
+        };
+        self.assets.get().font.draw(
+            framebuffer,
+            &camera,
+            &format!("practice - slot {slot}/{total} ({state})"),
+            vec2::splat(geng::TextAlign::CENTER),
+            mat3::translate(vec2(0.0, 4.5)) * mat3::scale_uniform(0.4),
+            Rgba::new(0.0, 0.0, 0.0, 0.5),
+        );
+    }
+}
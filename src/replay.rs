@@ -107,15 +107,84 @@ mod v0 {
     pub struct History(pub VecDeque<HistoryEntry>);
 }
 
+mod v1 {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct PhysicsState {
+        pub radius: f32,
+        pub pos: vec2<f32>,
+        pub vel: vec2<f32>,
+        pub rot: f32,
+        pub w: f32,
+        pub fart_type: String,
+        pub long_farting: bool,
+        pub fart_pressure: f32,
+        pub snow_layer: f32,
+        pub cannon_timer: Option<CannonTimer>,
+        pub stick_force: vec2<f32>,
+        pub bubble_timer: Option<f32>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct HistoryEntry {
+        pub timestamp: f32,
+        pub input: Input,
+        pub snapshot: PhysicsState,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct History {
+        pub customization: CustomizationOptions,
+        pub log: VecDeque<HistoryEntry>,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    pub struct PhysicsState {
+        pub radius: f32,
+        pub pos: vec2<f32>,
+        pub vel: vec2<f32>,
+        pub rot: f32,
+        pub w: f32,
+        pub fart_type: String,
+        pub long_farting: bool,
+        pub fart_pressure: f32,
+        pub snow_layer: f32,
+        pub cannon_timer: Option<CannonTimer>,
+        pub stick_force: vec2<f32>,
+        pub bubble_timer: Option<f32>,
+        pub fart_type_timer: Option<FartTypeTimer>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct HistoryEntry {
+        pub timestamp: f32,
+        pub input: Input,
+        pub snapshot: PhysicsState,
+    }
+
+    #[derive(Serialize, Deserialize, Clone)]
+    pub struct History {
+        pub customization: CustomizationOptions,
+        pub log: VecDeque<HistoryEntry>,
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum Versioned {
     V0(v0::History),
-    V1(History),
+    V1(v1::History),
+    V2(v2::History),
+    V3(History),
 }
 
 impl From<History> for Versioned {
     fn from(value: History) -> Self {
-        Self::V1(value)
+        Self::V3(value)
     }
 }
 
@@ -143,11 +212,67 @@ impl From<Versioned> for History {
                             cannon_timer: entry.snapshot.cannon_timer,
                             stick_force: entry.snapshot.stick_force,
                             bubble_timer: entry.snapshot.bubble_timer,
+                            fart_type_timer: None,
+                            portal_cooldown: None,
+                        },
+                    })
+                    .collect(),
+            },
+            Versioned::V1(history) => Self {
+                customization: history.customization,
+                log: history
+                    .log
+                    .into_iter()
+                    .map(|entry| HistoryEntry {
+                        timestamp: entry.timestamp,
+                        input: entry.input,
+                        snapshot: PhysicsState {
+                            radius: entry.snapshot.radius,
+                            pos: entry.snapshot.pos,
+                            vel: entry.snapshot.vel,
+                            rot: entry.snapshot.rot,
+                            w: entry.snapshot.w,
+                            fart_type: entry.snapshot.fart_type,
+                            long_farting: entry.snapshot.long_farting,
+                            fart_pressure: entry.snapshot.fart_pressure,
+                            snow_layer: entry.snapshot.snow_layer,
+                            cannon_timer: entry.snapshot.cannon_timer,
+                            stick_force: entry.snapshot.stick_force,
+                            bubble_timer: entry.snapshot.bubble_timer,
+                            fart_type_timer: None,
+                            portal_cooldown: None,
+                        },
+                    })
+                    .collect(),
+            },
+            Versioned::V2(history) => Self {
+                customization: history.customization,
+                log: history
+                    .log
+                    .into_iter()
+                    .map(|entry| HistoryEntry {
+                        timestamp: entry.timestamp,
+                        input: entry.input,
+                        snapshot: PhysicsState {
+                            radius: entry.snapshot.radius,
+                            pos: entry.snapshot.pos,
+                            vel: entry.snapshot.vel,
+                            rot: entry.snapshot.rot,
+                            w: entry.snapshot.w,
+                            fart_type: entry.snapshot.fart_type,
+                            long_farting: entry.snapshot.long_farting,
+                            fart_pressure: entry.snapshot.fart_pressure,
+                            snow_layer: entry.snapshot.snow_layer,
+                            cannon_timer: entry.snapshot.cannon_timer,
+                            stick_force: entry.snapshot.stick_force,
+                            bubble_timer: entry.snapshot.bubble_timer,
+                            fart_type_timer: entry.snapshot.fart_type_timer,
+                            portal_cooldown: None,
                         },
                     })
                     .collect(),
             },
-            Versioned::V1(value) => value,
+            Versioned::V3(value) => value,
         }
     }
 }
@@ -0,0 +1,243 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SaveSlot {
+    pub name: String,
+    /// Identity of the level this save was made on, see [LevelInfo::identity]
+    pub level: u64,
+    /// Unix time in seconds, not available on the web
+    pub timestamp: Option<u64>,
+    pub state: PhysicsState,
+    pub simulation_time: f32,
+    pub best_progress: f32,
+    pub best_time: Option<f32>,
+}
+
+#[derive(Clone)]
+pub enum LoadMenuMessage {
+    Load(usize),
+    Delete(usize),
+    NewGame,
+    Back,
+}
+
+fn unix_timestamp() -> Option<u64> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|duration| duration.as_secs())
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        None
+    }
+}
+
+fn format_age(timestamp: Option<u64>) -> String {
+    let (Some(now), Some(timestamp)) = (unix_timestamp(), timestamp) else {
+        return String::new();
+    };
+    let minutes = now.saturating_sub(timestamp) / 60;
+    let hours = minutes / 60;
+    let days = hours / 24;
+    if days != 0 {
+        format!("{days}d ago")
+    } else if hours != 0 {
+        format!("{hours}h ago")
+    } else {
+        format!("{minutes}m ago")
+    }
+}
+
+/// Loads save slots, discarding the ones made on a different version of the level
+/// and migrating the save from before there were slots
+pub fn load_save_slots(level: u64) -> Vec<SaveSlot> {
+    let mut slots: Vec<SaveSlot> = preferences::load("saves").unwrap_or_default();
+    let total = slots.len();
+    slots.retain(|slot| slot.level == level);
+    if slots.len() != total {
+        log::info!(
+            "Discarded {} saves made on another level",
+            total - slots.len()
+        );
+        preferences::save("saves", &slots);
+    }
+    // The single save from before slots existed becomes a slot of its own
+    if let Some(state) = preferences::load::<Option<PhysicsState>>("save").flatten() {
+        let name = (1..)
+            .map(|index| format!("save {index}"))
+            .find(|name| !slots.iter().any(|slot| &slot.name == name))
+            .unwrap();
+        log::info!("Moved the old save into {name:?}");
+        slots.push(SaveSlot {
+            name,
+            level,
+            timestamp: None,
+            state,
+            simulation_time: preferences::load("simulation_time").unwrap_or(0.0),
+            best_progress: 0.0,
+            best_time: None,
+        });
+        preferences::save("saves", &slots);
+        preferences::save("save", &None::<PhysicsState>);
+        preferences::save("simulation_time", &None::<f32>);
+    }
+    slots
+}
+
+impl Game {
+    pub fn write_save(&mut self) {
+        let Some(name) = self.current_save.clone() else { return };
+        let Some(me) = self.my_guy.and_then(|id| self.guys.get(&id)) else { return };
+        let slot = SaveSlot {
+            name,
+            level: self.level_identity,
+            timestamp: unix_timestamp(),
+            state: me.state.clone(),
+            simulation_time: self.simulation_time,
+            best_progress: self.best_progress,
+            best_time: self.best_time,
+        };
+        match self
+            .save_slots
+            .iter_mut()
+            .find(|other| other.name == slot.name)
+        {
+            Some(existing) => *existing = slot,
+            None => self.save_slots.push(slot),
+        }
+        preferences::save("saves", &self.save_slots);
+        preferences::save("current_save", &self.current_save);
+    }
+
+    pub fn load_save(&mut self, index: usize) {
+        let slot = self.save_slots[index].clone();
+        self.respawn_my_guy();
+        if let Some(me) = self.my_guy.and_then(|id| self.guys.get_mut(&id)) {
+            me.state = slot.state;
+        }
+        self.simulation_time = slot.simulation_time;
+        self.best_progress = slot.best_progress;
+        self.best_time = slot.best_time;
+        self.current_save = Some(slot.name);
+    }
+
+    pub fn new_save(&mut self) {
+        let name = (1..)
+            .map(|index| format!("save {index}"))
+            .find(|name| !self.save_slots.iter().any(|slot| &slot.name == name))
+            .unwrap();
+        self.respawn_my_guy();
+        self.best_progress = 0.0;
+        self.best_time = None;
+        self.current_save = Some(name);
+        self.write_save();
+    }
+
+    fn load_menu_buttons(&self) -> Vec<ui::Button<LoadMenuMessage>> {
+        let mut buttons = Vec::new();
+        for (index, slot) in self.save_slots.iter().enumerate() {
+            let y = 2.0 - index as f32;
+            let progress = (slot.best_progress * 100.0).round() as i32;
+            let mut text = format!("{} - {progress}%", slot.name);
+            if let Some(time) = slot.best_time {
+                let seconds = time.round() as i32;
+                text += &format!(" - {}:{:02}", seconds / 60, seconds % 60);
+            }
+            let age = format_age(slot.timestamp);
+            if !age.is_empty() {
+                text += &format!(" - {age}");
+            }
+            if Some(&slot.name) == self.current_save.as_ref() {
+                text = format!("> {text}");
+            }
+            buttons.push(ui::Button::new(
+                &text,
+                vec2(0.0, y),
+                0.5,
+                0.5,
+                LoadMenuMessage::Load(index),
+            ));
+            buttons.push(ui::Button::new(
+                "x",
+                vec2(-6.5, y),
+                0.5,
+                0.0,
+                LoadMenuMessage::Delete(index),
+            ));
+        }
+        buttons.push(ui::Button::new(
+            "new game",
+            vec2(0.0, -3.0),
+            0.7,
+            0.5,
+            LoadMenuMessage::NewGame,
+        ));
+        buttons.push(ui::Button::new(
+            "back",
+            vec2(0.0, -4.0),
+            0.7,
+            0.5,
+            LoadMenuMessage::Back,
+        ));
+        buttons
+    }
+
+    pub fn draw_load_menu(&mut self, framebuffer: &mut ugli::Framebuffer) {
+        let camera = geng::Camera2d {
+            center: vec2::ZERO,
+            rotation: 0.0,
+            fov: 10.0,
+        };
+        self.assets.get().font.draw(
+            framebuffer,
+            &camera,
+            "load game",
+            vec2::splat(geng::TextAlign::CENTER),
+            mat3::translate(vec2(0.0, 3.5)),
+            Rgba::new(0.5, 0.5, 1.0, 1.0),
+        );
+        let buttons = self.load_menu_buttons();
+        self.ui_controller.draw(framebuffer, &camera, buttons);
+    }
+
+    pub fn handle_load_menu_event(&mut self, event: &geng::Event) {
+        let buttons = self.load_menu_buttons();
+        let msgs = self.ui_controller.handle_event(event, buttons).into_iter();
+        let mut msgs = msgs.chain(
+            matches!(
+                event,
+                geng::Event::KeyDown {
+                    key: geng::Key::Escape,
+                }
+            )
+            .then_some(LoadMenuMessage::Back),
+        );
+        // Buttons are rebuilt after any change, so only the first message is handled
+        if let Some(msg) = msgs.next() {
+            match msg {
+                LoadMenuMessage::Load(index) => {
+                    self.load_save(index);
+                    self.show_load_menu = false;
+                }
+                LoadMenuMessage::Delete(index) => {
+                    let slot = self.save_slots.remove(index);
+                    if Some(&slot.name) == self.current_save.as_ref() {
+                        // Otherwise the next autosave would bring the slot back
+                        self.current_save = None;
+                    }
+                    preferences::save("saves", &self.save_slots);
+                }
+                LoadMenuMessage::NewGame => {
+                    self.new_save();
+                    self.show_load_menu = false;
+                }
+                LoadMenuMessage::Back => {
+                    self.show_load_menu = false;
+                }
+            }
+        }
+    }
+}
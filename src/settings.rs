@@ -0,0 +1,224 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Settings {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub mute_music: bool,
+    pub show_names: bool,
+    pub show_leaderboard: bool,
+    /// Radius (in pixels) of the cursor control scheme, disabled if `None`
+    pub accessibility: Option<f32>,
+    pub camera_fov: f32,
+    pub gamepad_deadzone: f32,
+    /// A single button activates the option currently highlighted by scanning
+    #[serde(default)]
+    pub one_switch: bool,
+    /// Seconds between scanning steps
+    #[serde(default = "default_scan_interval")]
+    pub scan_interval: f32,
+    /// Roll buttons toggle rolling instead of being held
+    #[serde(default)]
+    pub toggle_roll: bool,
+    #[serde(default = "default_game_speed")]
+    pub game_speed: f32,
+}
+
+fn default_scan_interval() -> f32 {
+    1.0
+}
+
+fn default_game_speed() -> f32 {
+    1.0
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum SettingsItem {
+    MasterVolume,
+    MusicVolume,
+    SfxVolume,
+    MuteMusic,
+    ShowNames,
+    ShowLeaderboard,
+    Accessibility,
+    CameraFov,
+    GamepadDeadzone,
+    OneSwitch,
+    ScanInterval,
+    ToggleRoll,
+    GameSpeed,
+}
+
+impl SettingsItem {
+    pub const ALL: [Self; 13] = [
+        Self::MasterVolume,
+        Self::MusicVolume,
+        Self::SfxVolume,
+        Self::MuteMusic,
+        Self::ShowNames,
+        Self::ShowLeaderboard,
+        Self::Accessibility,
+        Self::CameraFov,
+        Self::GamepadDeadzone,
+        Self::OneSwitch,
+        Self::ScanInterval,
+        Self::ToggleRoll,
+        Self::GameSpeed,
+    ];
+}
+
+/// Moves `value` by `delta` steps inside `range`, wrapping around if `wrap` is set
+fn step(value: f32, delta: i32, step: f32, min: f32, max: f32, wrap: bool) -> f32 {
+    let steps = ((max - min) / step).round() as i32;
+    let current = ((value - min) / step).round() as i32;
+    let new = current + delta;
+    let new = if wrap {
+        new.rem_euclid(steps + 1)
+    } else {
+        new.clamp(0, steps)
+    };
+    min + new as f32 * step
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "on"
+    } else {
+        "off"
+    }
+}
+
+fn percent(value: f32) -> String {
+    format!("{}%", (value * 100.0).round() as i32)
+}
+
+impl Settings {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            master_volume: config.volume,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+            mute_music: false,
+            show_names: true,
+            show_leaderboard: true,
+            accessibility: None,
+            camera_fov: config.camera_fov,
+            gamepad_deadzone: 0.1,
+            one_switch: false,
+            scan_interval: default_scan_interval(),
+            toggle_roll: false,
+            game_speed: default_game_speed(),
+        }
+    }
+
+    pub fn load(config: &Config) -> Self {
+        preferences::load("settings").unwrap_or_else(|| Self::new(config))
+    }
+
+    pub fn save(&self) {
+        preferences::save("settings", self);
+    }
+
+    /// Whether any of the assists that make the game easier are enabled
+    pub fn assisted(&self) -> bool {
+        self.one_switch || self.toggle_roll || self.game_speed < 1.0
+    }
+
+    pub fn music_volume(&self) -> f32 {
+        if self.mute_music {
+            return 0.0;
+        }
+        self.master_volume * self.music_volume
+    }
+
+    pub fn sfx_volume(&self) -> f32 {
+        self.master_volume * self.sfx_volume
+    }
+
+    pub fn label(&self, item: SettingsItem) -> String {
+        match item {
+            SettingsItem::MasterVolume => format!("volume: {}", percent(self.master_volume)),
+            SettingsItem::MusicVolume => format!("music volume: {}", percent(self.music_volume)),
+            SettingsItem::SfxVolume => format!("sfx volume: {}", percent(self.sfx_volume)),
+            SettingsItem::MuteMusic => format!("music: {}", on_off(!self.mute_music)),
+            SettingsItem::ShowNames => format!("names: {}", on_off(self.show_names)),
+            SettingsItem::ShowLeaderboard => {
+                format!("leaderboard: {}", on_off(self.show_leaderboard))
+            }
+            SettingsItem::Accessibility => match self.accessibility {
+                Some(radius) => format!("accessibility: {}px", radius.round() as i32),
+                None => "accessibility: off".to_owned(),
+            },
+            SettingsItem::CameraFov => format!("camera fov: {:.1}", self.camera_fov),
+            SettingsItem::GamepadDeadzone => {
+                format!("gamepad deadzone: {}", percent(self.gamepad_deadzone))
+            }
+            SettingsItem::OneSwitch => format!("one switch: {}", on_off(self.one_switch)),
+            SettingsItem::ScanInterval => format!("scan interval: {:.2}s", self.scan_interval),
+            SettingsItem::ToggleRoll => format!("toggle roll: {}", on_off(self.toggle_roll)),
+            SettingsItem::GameSpeed => format!("game speed: {}", percent(self.game_speed)),
+        }
+    }
+
+    pub fn adjust(&mut self, item: SettingsItem, delta: i32, wrap: bool) {
+        match item {
+            SettingsItem::MasterVolume => {
+                self.master_volume = step(self.master_volume, delta, 0.1, 0.0, 1.0, wrap);
+            }
+            SettingsItem::MusicVolume => {
+                self.music_volume = step(self.music_volume, delta, 0.1, 0.0, 1.0, wrap);
+            }
+            SettingsItem::SfxVolume => {
+                self.sfx_volume = step(self.sfx_volume, delta, 0.1, 0.0, 1.0, wrap);
+            }
+            SettingsItem::MuteMusic => self.mute_music = !self.mute_music,
+            SettingsItem::ShowNames => self.show_names = !self.show_names,
+            SettingsItem::ShowLeaderboard => self.show_leaderboard = !self.show_leaderboard,
+            SettingsItem::Accessibility => {
+                // Zero radius means the scheme is turned off
+                let radius = step(
+                    self.accessibility.unwrap_or(0.0),
+                    delta,
+                    50.0,
+                    0.0,
+                    500.0,
+                    wrap,
+                );
+                self.accessibility = (radius > 0.0).then_some(radius);
+            }
+            SettingsItem::CameraFov => {
+                self.camera_fov = step(self.camera_fov, delta, 0.5, 3.0, 10.0, wrap);
+            }
+            SettingsItem::GamepadDeadzone => {
+                self.gamepad_deadzone = step(self.gamepad_deadzone, delta, 0.05, 0.0, 0.5, wrap);
+            }
+            SettingsItem::OneSwitch => self.one_switch = !self.one_switch,
+            SettingsItem::ScanInterval => {
+                self.scan_interval = step(self.scan_interval, delta, 0.25, 0.5, 3.0, wrap);
+            }
+            SettingsItem::ToggleRoll => self.toggle_roll = !self.toggle_roll,
+            SettingsItem::GameSpeed => {
+                self.game_speed = step(self.game_speed, delta, 0.1, 0.5, 1.0, wrap);
+            }
+        }
+        self.save();
+    }
+}
+
+impl Game {
+    /// The command line option overrides the setting without being saved
+    pub fn accessibility_radius(&self) -> Option<f32> {
+        self.opt.accessibility.or(self.settings.accessibility)
+    }
+
+    /// Like [Settings::label], but shows the command line override instead of the saved value
+    pub fn setting_label(&self, item: SettingsItem) -> String {
+        match (item, self.opt.accessibility) {
+            (SettingsItem::Accessibility, Some(radius)) => {
+                format!("accessibility: {}px (command line)", radius.round() as i32)
+            }
+            _ => self.settings.label(item),
+        }
+    }
+}
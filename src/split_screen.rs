@@ -0,0 +1,291 @@
+use super::*;
+
+pub const MAX_LOCAL_PLAYERS: usize = 4;
+
+/// Left, right and fart keys for keyboard local players
+const LOCAL_KEY_SETS: [[geng::Key; 3]; 3] = [
+    [geng::Key::F, geng::Key::G, geng::Key::T],
+    [geng::Key::J, geng::Key::K, geng::Key::U],
+    [geng::Key::B, geng::Key::N, geng::Key::O],
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LocalControls {
+    Gamepad(gilrs::GamepadId),
+    /// Index into the keyboard key sets
+    Keys(usize),
+}
+
+/// Additional player sharing this screen, the first player is always `my_guy`.
+/// Local players are only simulated on this client and are not sent to the server.
+pub struct LocalPlayer {
+    pub id: Id,
+    pub controls: LocalControls,
+    pub camera: geng::Camera2d,
+    pub camera_director: CameraDirector,
+    pub best_progress: f32,
+    pub best_time: Option<f32>,
+    /// Own run clock, like `simulation_time` for the first player
+    pub run_time: f32,
+}
+
+/// Splits the screen into a viewport for every player
+fn viewports(count: usize, size: vec2<usize>) -> Vec<Aabb2<usize>> {
+    let (columns, rows) = match count {
+        1 => (1, 1),
+        2 => (2, 1),
+        _ => (2, 2),
+    };
+    let cell = vec2(size.x / columns, size.y / rows);
+    (0..count)
+        .map(|index| {
+            let column = index % columns;
+            // First players go on top
+            let row = rows - 1 - index / columns;
+            Aabb2::point(vec2(column * cell.x, row * cell.y)).extend_positive(cell)
+        })
+        .collect()
+}
+
+impl Game {
+    pub fn is_local_player(&self, id: Id) -> bool {
+        self.local_players.iter().any(|player| player.id == id)
+    }
+
+    fn local_player_positions(&self) -> Vec<vec2<f32>> {
+        self.my_guy
+            .into_iter()
+            .chain(self.local_players.iter().map(|player| player.id))
+            .filter_map(|id| self.guys.get(&id))
+            .map(|guy| guy.state.pos)
+            .collect()
+    }
+
+    /// Whether local players are too far apart to share one camera
+    pub fn split_screen(&self) -> bool {
+        if self.local_players.is_empty() {
+            return false;
+        }
+        let positions = self.local_player_positions();
+        if positions.is_empty() {
+            return false;
+        }
+        let bounds = Aabb2::points_bounding_box(positions);
+        let fov = self.settings.camera_fov;
+        let aspect = self.framebuffer_size.x / self.framebuffer_size.y;
+        bounds.height() > fov * 0.6 || bounds.width() > fov * aspect * 0.6
+    }
+
+    pub fn add_local_player(&mut self, controls: LocalControls) {
+        if self.local_players.len() + 1 >= MAX_LOCAL_PLAYERS {
+            return;
+        }
+        let index = (0..).find(|&index| {
+            let id = Id::local_player(index);
+            !self.is_local_player(id)
+        });
+        let id = Id::local_player(index.unwrap());
+        let mut guy = Guy::new(id, self.level.spawn_point, true, &self.config);
+        guy.customization.name = format!("player {}", self.local_players.len() + 2);
+        self.guys.insert(guy);
+        self.local_players.push(LocalPlayer {
+            id,
+            controls,
+            camera: geng::Camera2d {
+                center: self.level.spawn_point,
+                rotation: 0.0,
+                fov: self.settings.camera_fov,
+            },
+            camera_director: default(),
+            best_progress: 0.0,
+            best_time: None,
+            run_time: 0.0,
+        });
+    }
+
+    pub fn remove_local_players(&mut self) {
+        for player in self.local_players.drain(..) {
+            self.guys.remove(&player.id);
+        }
+    }
+
+    fn respawn_local_player(&mut self, id: Id) {
+        let Some(guy) = self.guys.get_mut(&id) else { return };
+        let pos = guy.progress.checkpoint.unwrap_or(self.level.spawn_point);
+        let mut new_guy = Guy::new(id, pos, false, &self.config);
+        new_guy.customization = guy.customization.clone();
+        new_guy.progress = guy.progress.clone();
+        *guy = new_guy;
+    }
+
+    /// Handles joining and respawning, returns true if the event was used by local players
+    pub fn handle_local_players_event(&mut self, event: &geng::Event) -> bool {
+        match *event {
+            geng::Event::Gamepad(gilrs::Event {
+                id,
+                event: gilrs::EventType::ButtonPressed(button, ..),
+                ..
+            }) => {
+                let player = self
+                    .local_players
+                    .iter()
+                    .find(|player| player.controls == LocalControls::Gamepad(id))
+                    .map(|player| player.id);
+                if let Some(player) = player {
+                    if button == gilrs::Button::Select {
+                        self.respawn_local_player(player);
+                    }
+                    return true;
+                }
+                // Start on the first player's gamepad pauses, on any other one it joins
+                if button == gilrs::Button::Start
+                    && (self.active_gamepad.is_some() || self.keyboard_player_one)
+                    && self.active_gamepad != Some(id)
+                    && self.editor.is_none()
+                {
+                    self.add_local_player(LocalControls::Gamepad(id));
+                    return true;
+                }
+                false
+            }
+            geng::Event::Gamepad(gilrs::Event { id, .. }) => self
+                .local_players
+                .iter()
+                .any(|player| player.controls == LocalControls::Gamepad(id)),
+            geng::Event::KeyDown { key: geng::Key::F8 }
+                if self.editor.is_none() && !self.show_customizer =>
+            {
+                let free_set = (0..LOCAL_KEY_SETS.len()).find(|&set| {
+                    !self
+                        .local_players
+                        .iter()
+                        .any(|player| player.controls == LocalControls::Keys(set))
+                });
+                if let Some(set) = free_set {
+                    self.add_local_player(LocalControls::Keys(set));
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn update_local_players_input(&mut self) {
+        let paused = self.pause_menu.is_some();
+        for player in &self.local_players {
+            let Some(guy) = self.guys.get_mut(&player.id) else { continue };
+            guy.paused = paused;
+            let mut input = Input::default();
+            match player.controls {
+                LocalControls::Gamepad(id) => {
+                    let gilrs = self.geng.gilrs();
+                    let gamepad = gilrs.gamepad(id);
+                    if let Some(axis) = gamepad.axis_data(gilrs::Axis::LeftStickX) {
+                        let value = axis.value();
+                        if value.abs() >= self.settings.gamepad_deadzone {
+                            input.roll_left = (-value).max(0.0);
+                            input.roll_right = value.max(0.0);
+                        }
+                    }
+                    if let Some(button) = gamepad.button_data(gilrs::Button::South) {
+                        input.force_fart = button.is_pressed();
+                    }
+                }
+                LocalControls::Keys(set) => {
+                    let [left, right, fart] = LOCAL_KEY_SETS[set];
+                    let window = self.geng.window();
+                    if window.is_key_pressed(left) {
+                        input.roll_left = 1.0;
+                    }
+                    if window.is_key_pressed(right) {
+                        input.roll_right = 1.0;
+                    }
+                    input.force_fart = window.is_key_pressed(fart);
+                }
+            }
+            guy.input = input;
+        }
+    }
+
+    /// Advances the run clocks of local players that are still playing
+    pub fn update_local_players_time(&mut self, delta_time: f32) {
+        for player in &mut self.local_players {
+            let Some(guy) = self.guys.get(&player.id) else { continue };
+            if !guy.progress.finished && !guy.paused {
+                player.run_time += delta_time;
+            }
+        }
+    }
+
+    pub fn update_local_players(&mut self, delta_time: f32) {
+        for player in &mut self.local_players {
+            let Some(guy) = self.guys.get_mut(&player.id) else { continue };
+            let progress = self
+                .level
+                .progress_at(guy.state.pos)
+                .unwrap_or(guy.progress.current);
+            guy.progress.current = progress;
+            player.best_progress = player.best_progress.max(progress);
+            guy.progress.best = player.best_progress;
+            if guy.progress.finished && player.run_time < player.best_time.unwrap_or(1e9) {
+                player.best_time = Some(player.run_time);
+            }
+            guy.progress.best_time = player.best_time;
+        }
+        let ids: Vec<Id> = self.local_players.iter().map(|player| player.id).collect();
+        for (index, id) in ids.into_iter().enumerate() {
+            let player = &mut self.local_players[index];
+            let mut camera = player.camera.clone();
+            let mut director = mem::take(&mut player.camera_director);
+            self.direct_camera(&mut director, &mut camera, &[id], delta_time);
+            let player = &mut self.local_players[index];
+            player.camera = camera;
+            player.camera_director = director;
+        }
+    }
+
+    /// Draws the world once for every local player into their part of the screen
+    pub fn draw_split_screen(&mut self, framebuffer: &mut ugli::Framebuffer) {
+        let mut cameras = vec![(self.camera.clone(), self.camera_parallax)];
+        cameras.extend(
+            self.local_players
+                .iter()
+                .map(|player| (player.camera.clone(), player.camera_director.parallax)),
+        );
+        let viewports = viewports(cameras.len(), framebuffer.size());
+        let main_camera = (self.camera.clone(), self.camera_parallax);
+        let framebuffer_size = self.framebuffer_size;
+        // Textures are reused between frames while the viewports keep their size
+        let mut textures = mem::take(&mut self.split_screen_textures);
+        textures.retain(|texture| texture.size() == viewports[0].size());
+        for (index, ((camera, parallax), viewport)) in
+            cameras.into_iter().zip(viewports).enumerate()
+        {
+            if textures.len() <= index {
+                textures.push(ugli::Texture::new_uninitialized(
+                    self.geng.ugli(),
+                    viewport.size(),
+                ));
+            }
+            let texture = &mut textures[index];
+            {
+                let mut view_framebuffer = ugli::Framebuffer::new_color(
+                    self.geng.ugli(),
+                    ugli::ColorAttachment::Texture(texture),
+                );
+                self.camera = camera;
+                self.camera_parallax = parallax;
+                self.framebuffer_size = viewport.size().map(|x| x as f32);
+                self.draw_world(&mut view_framebuffer);
+            }
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                &geng::PixelPerfectCamera,
+                &draw2d::TexturedQuad::new(viewport.map(|x| x as f32), &*texture),
+            );
+        }
+        self.split_screen_textures = textures;
+        (self.camera, self.camera_parallax) = main_camera;
+        self.framebuffer_size = framebuffer_size;
+    }
+}
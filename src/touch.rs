@@ -0,0 +1,130 @@
+use super::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TouchZone {
+    Left,
+    Right,
+    Fart,
+    Pause,
+}
+
+impl TouchZone {
+    const ALL: [Self; 4] = [Self::Left, Self::Right, Self::Fart, Self::Pause];
+
+    /// Area of the zone as a fraction of the screen
+    fn area(&self) -> Aabb2<f32> {
+        match self {
+            Self::Left => Aabb2::from_corners(vec2(0.0, 0.0), vec2(0.25, 0.6)),
+            Self::Right => Aabb2::from_corners(vec2(0.25, 0.0), vec2(0.5, 0.6)),
+            Self::Fart => Aabb2::from_corners(vec2(0.5, 0.0), vec2(1.0, 0.6)),
+            Self::Pause => Aabb2::from_corners(vec2(0.9, 0.85), vec2(1.0, 1.0)),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Left => "<",
+            Self::Right => ">",
+            Self::Fart => "fart",
+            Self::Pause => "||",
+        }
+    }
+
+    fn at(position: vec2<f64>, framebuffer_size: vec2<f32>) -> Option<Self> {
+        let position = position.map(|x| x as f32) / framebuffer_size;
+        Self::ALL
+            .into_iter()
+            .find(|zone| zone.area().contains(position))
+    }
+}
+
+#[derive(Default)]
+pub struct TouchControls {
+    /// Controls are only shown once a touch event was seen
+    pub enabled: bool,
+    touches: Vec<geng::Touch>,
+}
+
+impl TouchControls {
+    fn is_pressed(&self, zone: TouchZone, framebuffer_size: vec2<f32>) -> bool {
+        self.touches
+            .iter()
+            .any(|touch| TouchZone::at(touch.position, framebuffer_size) == Some(zone))
+    }
+
+    /// Adds touch controls to the input, every finger can press its own zone
+    pub fn apply_input(&self, input: &mut Input, framebuffer_size: vec2<f32>) {
+        if self.is_pressed(TouchZone::Left, framebuffer_size) {
+            input.roll_left += 1.0;
+        }
+        if self.is_pressed(TouchZone::Right, framebuffer_size) {
+            input.roll_right += 1.0;
+        }
+        if self.is_pressed(TouchZone::Fart, framebuffer_size) {
+            input.force_fart = true;
+        }
+    }
+}
+
+impl Game {
+    pub fn handle_touch_event(&mut self, event: &geng::Event) {
+        match *event {
+            geng::Event::TouchStart(touch) => {
+                self.touch_controls.enabled = true;
+                self.touch_controls.touches.push(touch);
+                if TouchZone::at(touch.position, self.framebuffer_size) == Some(TouchZone::Pause)
+                    && !self.show_customizer
+                    && self.pause_menu.is_none()
+                    && self.editor.is_none()
+                {
+                    self.open_pause_menu();
+                }
+            }
+            geng::Event::TouchMove(touch) => {
+                for other in &mut self.touch_controls.touches {
+                    if other.id == touch.id {
+                        *other = touch;
+                    }
+                }
+            }
+            geng::Event::TouchEnd(touch) => {
+                self.touch_controls
+                    .touches
+                    .retain(|other| other.id != touch.id);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn draw_touch_controls(&self, framebuffer: &mut ugli::Framebuffer) {
+        if !self.touch_controls.enabled || self.show_customizer || self.pause_menu.is_some() {
+            return;
+        }
+        let framebuffer_size = framebuffer.size().map(|x| x as f32);
+        for zone in TouchZone::ALL {
+            let area = zone.area();
+            let area =
+                Aabb2::from_corners(area.min * framebuffer_size, area.max * framebuffer_size)
+                    .extend_uniform(-framebuffer_size.y * 0.01);
+            let alpha = if self.touch_controls.is_pressed(zone, framebuffer_size) {
+                0.3
+            } else {
+                0.1
+            };
+            self.geng.draw2d().draw2d(
+                framebuffer,
+                &geng::PixelPerfectCamera,
+                &draw2d::Quad::new(area, Rgba::new(0.5, 0.5, 1.0, alpha)),
+            );
+            self.assets.get().font.draw(
+                framebuffer,
+                &geng::PixelPerfectCamera,
+                zone.label(),
+                vec2::splat(geng::TextAlign::CENTER),
+                mat3::translate(area.center())
+                    * mat3::scale_uniform(area.height().min(area.width()) * 0.3),
+                Rgba::new(0.5, 0.5, 1.0, alpha * 2.0),
+            );
+        }
+    }
+}
@@ -24,12 +24,141 @@ impl<T> Button<T> {
     }
 }
 
+/// Single line text editing with a cursor, driven by the window's text events
+pub struct TextInput {
+    geng: Geng,
+    /// Cursor position in chars
+    cursor: usize,
+    editing: bool,
+    max_len: usize,
+    is_valid_char: fn(char) -> bool,
+}
+
+fn byte_index(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(index, _)| index)
+}
+
+/// Text that was inserted into `old` to get `new`, if that is the only change
+fn inserted_text(old: &str, new: &str) -> Option<String> {
+    let old: Vec<char> = old.chars().collect();
+    let new: Vec<char> = new.chars().collect();
+    if new.len() <= old.len() {
+        return None;
+    }
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    if prefix + suffix != old.len() {
+        return None;
+    }
+    Some(new[prefix..new.len() - suffix].iter().collect())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn clipboard_text() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn clipboard_text() -> Option<String> {
+    // The browser delivers pasted text as a text edit event
+    None
+}
+
+impl TextInput {
+    pub fn new(geng: &Geng, max_len: usize, is_valid_char: fn(char) -> bool) -> Self {
+        Self {
+            geng: geng.clone(),
+            cursor: 0,
+            editing: false,
+            max_len,
+            is_valid_char,
+        }
+    }
+    pub fn set_editing(&mut self, text: &str, editing: bool) {
+        if self.editing == editing {
+            return;
+        }
+        self.editing = editing;
+        if editing {
+            self.cursor = text.chars().count();
+            self.geng.window().start_text_edit(text);
+        } else {
+            self.geng.window().stop_text_edit();
+        }
+    }
+    fn insert(&mut self, text: &mut String, inserted: &str) {
+        for c in inserted.chars().filter(|&c| (self.is_valid_char)(c)) {
+            if text.chars().count() >= self.max_len {
+                break;
+            }
+            text.insert(byte_index(text, self.cursor), c);
+            self.cursor += 1;
+        }
+    }
+    pub fn handle_event(&mut self, text: &mut String, event: &geng::Event) {
+        if !self.editing {
+            return;
+        }
+        let len = text.chars().count();
+        self.cursor = self.cursor.min(len);
+        match event {
+            geng::Event::EditText(new_text) => {
+                // The window keeps its own copy of the text, only take what was typed
+                if let Some(inserted) = inserted_text(text, new_text) {
+                    self.insert(text, &inserted);
+                }
+            }
+            geng::Event::KeyDown { key } => match key {
+                geng::Key::Backspace if self.cursor > 0 => {
+                    self.cursor -= 1;
+                    text.remove(byte_index(text, self.cursor));
+                }
+                geng::Key::Delete if self.cursor < len => {
+                    text.remove(byte_index(text, self.cursor));
+                }
+                geng::Key::Left => self.cursor = self.cursor.saturating_sub(1),
+                geng::Key::Right => self.cursor = (self.cursor + 1).min(len),
+                geng::Key::Home => self.cursor = 0,
+                geng::Key::End => self.cursor = len,
+                geng::Key::V if self.geng.window().is_key_pressed(geng::Key::LCtrl) => {
+                    if let Some(pasted) = clipboard_text() {
+                        self.insert(text, &pasted);
+                    }
+                }
+                _ => return,
+            },
+            _ => return,
+        }
+        self.geng.window().start_text_edit(text);
+    }
+    /// Text with the cursor shown
+    pub fn display(&self, text: &str) -> String {
+        let mut text = text.to_owned();
+        if self.editing {
+            let index = byte_index(&text, self.cursor.min(text.chars().count()));
+            text.insert(index, '|');
+        }
+        text
+    }
+}
+
 pub struct Controller {
     geng: Geng,
     assets: AssetsHandle,
     mouse: vec2<f32>,
     camera: geng::Camera2d,
     framebuffer_size: vec2<f32>,
+    /// Button selected using keyboard or gamepad
+    selected: Option<usize>,
 }
 
 impl Controller {
@@ -44,8 +173,18 @@ impl Controller {
                 fov: 1.0,
             },
             framebuffer_size: vec2(1.0, 1.0),
+            selected: None,
         }
     }
+    pub fn has_selection(&self) -> bool {
+        self.selected.is_some()
+    }
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+    pub fn reset_selection(&mut self) {
+        self.selected = None;
+    }
     pub fn draw<T>(
         &mut self,
         framebuffer: &mut ugli::Framebuffer,
@@ -54,9 +193,9 @@ impl Controller {
     ) {
         self.camera = camera.clone();
         self.framebuffer_size = framebuffer.size().map(|x| x as f32);
-        for button in buttons {
+        for (index, button) in buttons.into_iter().enumerate() {
             let mut position = button.position;
-            let hovered = button.aabb().contains(self.mouse);
+            let hovered = button.aabb().contains(self.mouse) || self.selected == Some(index);
             if hovered
                 && self
                     .geng
@@ -91,6 +230,7 @@ impl Controller {
                 self.mouse = self
                     .camera
                     .screen_to_world(self.framebuffer_size, position.map(|x| x as f32));
+                self.selected = None;
             }
             geng::Event::TouchStart(touch)
             | geng::Event::TouchMove(touch)
@@ -101,6 +241,32 @@ impl Controller {
             }
             _ => {}
         }
+        let navigation = match *event {
+            geng::Event::KeyDown { key: geng::Key::Up }
+            | geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::ButtonPressed(gilrs::Button::DPadUp, ..),
+                ..
+            }) => Some(-1),
+            geng::Event::KeyDown {
+                key: geng::Key::Down,
+            }
+            | geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::ButtonPressed(gilrs::Button::DPadDown, ..),
+                ..
+            }) => Some(1),
+            _ => None,
+        };
+        if let Some(delta) = navigation {
+            if !buttons.is_empty() {
+                let len = buttons.len() as isize;
+                let current = match self.selected {
+                    Some(index) => index as isize,
+                    None if delta > 0 => -1,
+                    None => 0,
+                };
+                self.selected = Some((current + delta).rem_euclid(len) as usize);
+            }
+        }
         let mut result = Vec::new();
         match *event {
             geng::Event::MouseUp {
@@ -109,12 +275,23 @@ impl Controller {
             }
             | geng::Event::TouchEnd { .. } => {
                 if let Some(button) = buttons
-                    .into_iter()
+                    .iter()
                     .find(|button| button.aabb().contains(self.mouse))
                 {
                     result.push(button.message.clone());
                 }
             }
+            geng::Event::KeyDown {
+                key: geng::Key::Enter,
+            }
+            | geng::Event::Gamepad(gilrs::Event {
+                event: gilrs::EventType::ButtonPressed(gilrs::Button::South, ..),
+                ..
+            }) => {
+                if let Some(button) = self.selected.and_then(|index| buttons.get(index)) {
+                    result.push(button.message.clone());
+                }
+            }
             _ => {}
         }
         result
@@ -78,47 +78,53 @@ pub fn inside_triangle(p: vec2<f32>, tri: [vec2<f32>; 3]) -> bool {
     true
 }
 
+/// Part of the circle covered by the triangle, from 0 to 1
 pub fn circle_triangle_intersect_percentage(
     center: vec2<f32>,
     radius: f32,
     tri: [vec2<f32>; 3],
 ) -> f32 {
-    static RNG: once_cell::sync::Lazy<Vec<vec2<f32>>> = once_cell::sync::Lazy::new(|| {
-        (0..100)
-            .map(|_| thread_rng().gen_circle(vec2::ZERO, 1.0))
-            .collect()
-    });
-    RNG.iter()
-        .filter(|&&p| inside_triangle(center + p * radius, tri))
-        .count() as f32
-        / RNG.len() as f32
+    if radius < EPS {
+        let [a, b, c] = tri;
+        let inside = inside_triangle(center, [a, b, c]) || inside_triangle(center, [a, c, b]);
+        return inside as u8 as f32;
+    }
+    let area = circle_triangle_intersect_area(center, radius, tri);
+    (area / (f32::PI * radius.sqr())).clamp(0.0, 1.0)
 }
 
+/// Exact area of the intersection of a circle and a triangle of any winding
 pub fn circle_triangle_intersect_area(center: vec2<f32>, radius: f32, tri: [vec2<f32>; 3]) -> f32 {
-    fn circle_point_area(r: f32, p: vec2<f32>) -> f32 {
-        let cos = p.x / r;
-        let sin = (1.0 - cos.sqr()).max(0.0).sqrt();
-        let tri = cos * sin * r.sqr() / 2.0;
-
-        let big_a = f32::atan2(p.y, p.x);
-        let smol_a = f32::acos(p.x / r) * p.y.signum();
-        let a = big_a - smol_a;
-        let arc = a * r.sqr() / 2.0;
-
-        tri + arc
-    }
-    fn circle_segment_area(r: f32, ps: [vec2<f32>; 2]) -> f32 {
-        let v = (ps[1] - ps[0]).normalize_or_zero();
-        let s = |p| circle_point_area(r, vec2(vec2::skew(v, p), vec2::dot(v, p)));
-        s(ps[0]) + s(ps[1])
+    // Signed area of the circle intersected with the triangle (center, a, b)
+    fn edge_area(r: f32, a: vec2<f32>, b: vec2<f32>) -> f32 {
+        // |a + (b - a) * t| = r
+        let d = b - a;
+        let qa = vec2::dot(d, d);
+        let qb = 2.0 * vec2::dot(a, d);
+        let qc = vec2::dot(a, a) - r.sqr();
+        let discriminant = qb.sqr() - 4.0 * qa * qc;
+        let (t1, t2) = if qa < EPS || discriminant <= 0.0 {
+            (0.0, 0.0)
+        } else {
+            let sqrt = discriminant.sqrt();
+            (
+                ((-qb - sqrt) / (2.0 * qa)).clamp(0.0, 1.0),
+                ((-qb + sqrt) / (2.0 * qa)).clamp(0.0, 1.0),
+            )
+        };
+        // The part between the intersections is a chord inside the circle,
+        // the rest of the edge is outside and only cuts out a sector
+        let at = |t: f32| a + d * t;
+        let sector = |p: vec2<f32>, q: vec2<f32>| {
+            f32::atan2(vec2::skew(p, q), vec2::dot(p, q)) * r.sqr() / 2.0
+        };
+        sector(a, at(t1)) + vec2::skew(at(t1), at(t2)) / 2.0 + sector(at(t2), b)
     }
     let mut sum = 0.0;
     for i in 0..3 {
-        let a = tri[i] - center;
-        let b = tri[(i + 1) % 3] - center;
-        sum += circle_segment_area(radius, [a, b]);
+        sum += edge_area(radius, tri[i] - center, tri[(i + 1) % 3] - center);
     }
-    sum
+    sum.abs()
 }
 
 pub fn ray_hit_time(
@@ -130,3 +136,91 @@ pub fn ray_hit_time(
     // dot(ray_start + ray_vel * t - line_p, line_normal) = 0
     vec2::dot(line_p - ray_start, line_normal) / vec2::dot(ray_vel, line_normal)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monte Carlo estimate of the part of the circle covered by the triangle
+    fn sampled_percentage(center: vec2<f32>, radius: f32, tri: [vec2<f32>; 3]) -> f32 {
+        // Fixed seed linear congruential generator so the test is reproducible
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        let mut next = || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+        };
+        let mut inside_circle = 0;
+        let mut inside_both = 0;
+        while inside_circle < 200_000 {
+            let p = vec2(next(), next());
+            if p.len() > 1.0 {
+                continue;
+            }
+            inside_circle += 1;
+            let p = center + p * radius;
+            let [a, b, c] = tri;
+            if inside_triangle(p, [a, b, c]) || inside_triangle(p, [a, c, b]) {
+                inside_both += 1;
+            }
+        }
+        inside_both as f32 / inside_circle as f32
+    }
+
+    fn check(center: vec2<f32>, radius: f32, tri: [vec2<f32>; 3]) {
+        let [a, b, c] = tri;
+        for tri in [[a, b, c], [a, c, b]] {
+            let exact = circle_triangle_intersect_percentage(center, radius, tri);
+            let sampled = sampled_percentage(center, radius, tri);
+            assert!(
+                (exact - sampled).abs() < 0.01,
+                "exact {exact} sampled {sampled} for {center:?} {radius} {tri:?}",
+            );
+        }
+    }
+
+    const TRIANGLE: [vec2<f32>; 3] = [vec2(-5.0, -5.0), vec2(5.0, -5.0), vec2(0.0, 5.0)];
+
+    #[test]
+    fn circle_inside_triangle() {
+        check(vec2(0.0, -2.0), 1.0, TRIANGLE);
+        let percentage = circle_triangle_intersect_percentage(vec2(0.0, -2.0), 1.0, TRIANGLE);
+        assert!((percentage - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn circle_outside_triangle() {
+        check(vec2(10.0, 10.0), 1.0, TRIANGLE);
+        let percentage = circle_triangle_intersect_percentage(vec2(10.0, 10.0), 1.0, TRIANGLE);
+        assert!(percentage < 1e-4);
+    }
+
+    #[test]
+    fn circle_partially_covered() {
+        check(vec2(0.0, -5.0), 1.0, TRIANGLE);
+        check(vec2(-5.0, -5.0), 1.5, TRIANGLE);
+        check(vec2(2.5, 0.5), 2.0, TRIANGLE);
+    }
+
+    #[test]
+    fn triangle_inside_circle() {
+        check(vec2(0.0, 0.0), 10.0, TRIANGLE);
+        check(vec2(1.0, 0.0), 7.5, TRIANGLE);
+    }
+
+    #[test]
+    fn zero_radius() {
+        let [a, b, c] = TRIANGLE;
+        for tri in [[a, b, c], [a, c, b]] {
+            assert_eq!(
+                circle_triangle_intersect_percentage(vec2(0.0, 0.0), 0.0, tri),
+                1.0
+            );
+            assert_eq!(
+                circle_triangle_intersect_percentage(vec2(9.0, 0.0), 0.0, tri),
+                0.0
+            );
+        }
+    }
+}